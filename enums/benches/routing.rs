@@ -0,0 +1,80 @@
+/*
+  cargo bench --bench routing
+  How long a longest prefix match takes in the trie, next to the obvious way of doing it: check every
+  route and keep the longest one that holds the address. It prints its own numbers, there's no
+  bench framework behind it.
+  The routes and addresses come from hashing their index, so every run measures the same table.
+*/
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use enums::network::IpNetwork;
+use enums::routing::RoutingTable;
+use enums::IpAddrKind;
+
+const ROUTES: u64 = 100_000;
+const LOOKUPS: u64 = 100_000;
+// checking every route for every address is slow, it only gets the first 1000 addresses
+const SCAN_LOOKUPS: usize = 1_000;
+
+// splitmix64's finaliser, neighbouring inputs come out looking unrelated
+fn hash(mut value: u64) -> u64 {
+  value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+  value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+  value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+  value ^ (value >> 31)
+}
+
+// a quarter of them V6, and those all under 2001:db8::/32 so V6 routes overlap as often as V4 ones do
+fn address(seed: u64) -> IpAddrKind {
+  let bits = hash(seed);
+  if bits.is_multiple_of(4) {
+    IpAddrKind::V6([0x2001, 0xdb8, (bits >> 48) as u16, (bits >> 32) as u16, (bits >> 16) as u16, 0, 0, 1])
+  } else {
+    let [a, b, c, d] = ((bits >> 32) as u32).to_be_bytes();
+    IpAddrKind::V4(a, b, c, d)
+  }
+}
+
+// V4 routes from /8 to /32, V6 from /32 to /64
+fn route(index: u64) -> IpNetwork {
+  let address = address(index);
+  let length = (hash(index ^ 0xffff) % 25) as u8;
+  let prefix = if matches!(address, IpAddrKind::V4(..)) { 8 + length } else { 32 + length + length / 3 };
+  IpNetwork::new(address, prefix).unwrap().trunc()
+}
+
+fn scan(routes: &[IpNetwork], address: IpAddrKind) -> Option<u8> {
+  routes.iter().filter(|network| network.contains(address)).map(|network| network.prefix()).max()
+}
+
+// the time it took to look every address up and the prefix each one matched
+fn measure(addresses: &[IpAddrKind], find: impl Fn(IpAddrKind) -> Option<u8>) -> (Duration, Vec<Option<u8>>) {
+  let start = Instant::now();
+  let found: Vec<Option<u8>> = addresses.iter().map(|&address| black_box(find(black_box(address)))).collect();
+  (start.elapsed(), found)
+}
+
+fn main() {
+  let routes: Vec<IpNetwork> = (0..ROUTES).map(route).collect();
+  let addresses: Vec<IpAddrKind> = (ROUTES..ROUTES + LOOKUPS).map(address).collect();
+
+  let start = Instant::now();
+  let mut table = RoutingTable::new();
+  for &network in &routes {
+    table.insert(network, ());
+  }
+  println!("built a table of {} routes ({} distinct) in {:.2?}", routes.len(), table.len(), start.elapsed());
+
+  let sample = &addresses[..SCAN_LOOKUPS];
+  let (scan_time, scanned) = measure(sample, |address| scan(&routes, address));
+  let (trie_time, looked_up) = measure(sample, |address| table.lookup(address).map(|(network, _)| network.prefix()));
+  // different answers would make the comparison meaningless
+  assert_eq!(scanned, looked_up);
+  println!("{SCAN_LOOKUPS} lookups: scanning every route {scan_time:.2?}, trie {trie_time:.2?}");
+  println!("the trie is {:.0} times faster", scan_time.as_secs_f64() / trie_time.as_secs_f64());
+
+  let (all_time, found) = measure(&addresses, |address| table.lookup(address).map(|(network, _)| network.prefix()));
+  let matched = found.iter().flatten().count();
+  println!("{LOOKUPS} trie lookups ({matched} matched) in {all_time:.2?}, {:.0} ns each", all_time.as_nanos() as f64 / LOOKUPS as f64);
+}
@@ -0,0 +1,55 @@
+/*
+  cargo install cargo-fuzz (needs a nightly toolchain), then from the enums directory:
+    cargo +nightly fuzz run wire_decode
+  The input is treated as bytes off the wire. A Decoder given it all at once and one given it a
+  byte at a time have to hand out the same messages and errors, nothing may panic, and every
+  message that comes out has to encode and decode back to itself.
+*/
+#![no_main]
+
+use enums::wire::{self, Decoder, MessageReader};
+use enums::Message;
+use libfuzzer_sys::fuzz_target;
+
+// every message it can get out of the decoder (None for a frame that didn't decode), false once it hits a length it can't get past
+fn drain(decoder: &mut Decoder, out: &mut Vec<Option<Message>>) -> bool {
+  loop {
+    let pending = decoder.pending();
+    match decoder.next_message() {
+      Ok(Some(message)) => out.push(Some(message)),
+      Ok(None) => return true,
+      Err(_) if decoder.pending() < pending => out.push(None),
+      Err(_) => return false,
+    }
+  }
+}
+
+fuzz_target!(|data: &[u8]| {
+  let mut decoder = Decoder::new();
+  decoder.feed(data);
+  let mut at_once = Vec::new();
+  drain(&mut decoder, &mut at_once);
+
+  let mut decoder = Decoder::new();
+  let mut byte_by_byte = Vec::new();
+  for &byte in data {
+    decoder.feed(&[byte]);
+    if !drain(&mut decoder, &mut byte_by_byte) {
+      break;
+    }
+  }
+  assert_eq!(byte_by_byte, at_once);
+
+  if let Some(Some(first)) = at_once.first() {
+    assert_eq!(wire::decode(data).unwrap().map(|(message, _)| message).as_ref(), Some(first));
+  }
+  for message in at_once.iter().flatten() {
+    let frame = wire::encode(message).unwrap();
+    assert_eq!(wire::decode(&frame).unwrap(), Some((message.clone(), frame.len())));
+  }
+
+  // and the reader never panics or goes on forever
+  for result in MessageReader::new(data) {
+    let _ = result;
+  }
+});
@@ -0,0 +1,135 @@
+/*
+  Message as the mailbox of an actor: a worker is a thread that owns a Canvas and nothing else, and
+  the only way to change that canvas is to send the worker a Message over its channel. This is
+  where one enum beats the four separate QuitMessage/MoveMessage/... structs, a channel carries one
+  type and Message is one type that can be any of the four.
+
+  A worker applies each message with Message::call, tells the router it did on a shared
+  acknowledgement channel and stops after a Quit (or once nothing can send to it any more), handing
+  its canvas back as the thread's result. The Router starts N workers and fans messages out to
+  one of them or all of them; finish closes the mailboxes, waits for every worker and collects
+  the acknowledgements and final canvases.
+*/
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use crate::canvas::{Canvas, CanvasError};
+use crate::Message;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ack {
+  pub worker: usize,
+  // how many messages this worker has applied, counting this one, so 1 for its first
+  pub sequence: u64,
+  // this was the Quit and the worker has stopped
+  pub quit: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouterError {
+  UnknownWorker(usize),
+  // its thread has ended after a Quit, so nobody is reading the mailbox. A message sent while the
+  // Quit is still being applied just goes unread, there's no ack for it either way
+  WorkerStopped(usize),
+}
+
+impl fmt::Display for RouterError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      RouterError::UnknownWorker(worker) => write!(f, "there is no worker {worker}"),
+      RouterError::WorkerStopped(worker) => write!(f, "worker {worker} has already quit"),
+    }
+  }
+}
+
+impl std::error::Error for RouterError {}
+
+struct Worker {
+  mailbox: Sender<Message>,
+  thread: JoinHandle<Canvas>,
+}
+
+fn spawn_worker(id: usize, mut canvas: Canvas, acks: Sender<Ack>) -> Worker {
+  let (mailbox, inbox): (Sender<Message>, Receiver<Message>) = mpsc::channel();
+  let thread = thread::spawn(move || {
+    let mut sequence = 0;
+    // recv fails once every Sender is gone, which is a stop too
+    while let Ok(message) = inbox.recv() {
+      message.call(&mut canvas);
+      sequence += 1;
+      let quit = canvas.is_finished();
+      // the router may have stopped listening, the canvas is what matters so carry on
+      let _ = acks.send(Ack { worker: id, sequence, quit });
+      if quit {
+        break;
+      }
+    }
+    canvas
+  });
+  Worker { mailbox, thread }
+}
+
+pub struct Router {
+  workers: Vec<Worker>,
+  acks: Receiver<Ack>,
+}
+
+// what finish hands back: every acknowledgement in the order they arrived and each worker's canvas
+#[derive(Debug)]
+pub struct Report {
+  pub acks: Vec<Ack>,
+  pub canvases: Vec<Canvas>,
+}
+
+impl Router {
+  // starts `workers` threads, each with its own blank width x height canvas
+  pub fn new(workers: usize, width: usize, height: usize) -> Result<Self, CanvasError> {
+    let canvas = Canvas::new(width, height)?;
+    let (ack_sender, acks) = mpsc::channel();
+    let workers = (0..workers).map(|id| spawn_worker(id, canvas.clone(), ack_sender.clone())).collect();
+    Ok(Router { workers, acks })
+  }
+
+  pub fn workers(&self) -> usize {
+    self.workers.len()
+  }
+
+  pub fn send(&self, worker: usize, message: Message) -> Result<(), RouterError> {
+    let mailbox = &self.workers.get(worker).ok_or(RouterError::UnknownWorker(worker))?.mailbox;
+    mailbox.send(message).map_err(|_| RouterError::WorkerStopped(worker))
+  }
+
+  // a copy of the message to every worker still running, gives back how many that was
+  pub fn broadcast(&self, message: &Message) -> usize {
+    self.workers.iter().filter(|worker| worker.mailbox.send(message.clone()).is_ok()).count()
+  }
+
+  // the acknowledgements that have come in since the last call, without waiting for more
+  pub fn acks(&self) -> Vec<Ack> {
+    self.acks.try_iter().collect()
+  }
+
+  // waits for the next acknowledgement, None once every worker has stopped
+  pub fn wait_for_ack(&self) -> Option<Ack> {
+    self.acks.recv().ok()
+  }
+
+  /*
+    Closes every mailbox, so a worker that hasn't had a Quit stops once it has applied what's
+    already in it, then waits for all of them. acks holds the ones acks() and wait_for_ack()
+    didn't hand out already.
+  */
+  pub fn finish(self) -> Report {
+    let Router { workers, acks } = self;
+    let canvases = workers
+      .into_iter()
+      .map(|Worker { mailbox, thread }| {
+        drop(mailbox);
+        thread.join().expect("worker thread panicked")
+      })
+      .collect();
+    // every worker has returned so every Sender<Ack> is gone and this ends
+    Report { acks: acks.iter().collect(), canvases }
+  }
+}
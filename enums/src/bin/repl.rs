@@ -0,0 +1,103 @@
+/*
+  cargo run --bin repl [LOG]
+  Type messages as commands (help lists them) and they're applied to a canvas one at a time, the
+  same way a list of them is with Canvas::run. Besides the messages there is:
+    help       the syntax of every message
+    show       draws the canvas
+    history    every message applied so far, numbered
+    !N         applies message N from the history again
+    undo/redo  takes back the last message, or puts back the last one taken back
+  quit (or the end of the input) stops and draws the canvas one last time. With a LOG file every
+  step is kept in it, and starting again with the same file carries on from where it got to.
+*/
+use std::io::{self, BufRead, Write};
+use std::{env, process};
+
+use enums::command::{self, HELP};
+use enums::history::History;
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 10;
+const PROMPT: &str = "> ";
+
+fn main() {
+  let mut history = History::new(WIDTH, HEIGHT).unwrap_or_else(|error| {
+    eprintln!("{error}");
+    process::exit(1);
+  });
+  if let Some(path) = env::args_os().nth(1) {
+    history = history.with_log(&path).unwrap_or_else(|error| {
+      eprintln!("{}: {error}", path.to_string_lossy());
+      process::exit(1);
+    });
+  }
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+
+  println!("{WIDTH}x{HEIGHT} canvas, type help for the commands");
+  while !history.canvas().is_finished() {
+    print!("{PROMPT}");
+    io::stdout().flush().unwrap();
+    let Some(Ok(line)) = lines.next() else {
+      println!();
+      break;
+    };
+
+    let message = match line.trim() {
+      "" => continue,
+      "help" => {
+        println!("{HELP}\nhelp              this list\nshow              draw the canvas\nhistory           the messages so far\n!N                message N from the history again\nundo, redo        take back the last message, put it back");
+        continue;
+      }
+      "show" => {
+        print!("{}", history.canvas().to_text());
+        continue;
+      }
+      "history" => {
+        for (number, message) in history.applied().iter().enumerate() {
+          println!("{:>4}  {message}", number + 1);
+        }
+        continue;
+      }
+      step @ ("undo" | "redo") => {
+        let changed = if step == "undo" { history.undo() } else { history.redo() };
+        match changed {
+          Ok(true) => print!("{}", history.canvas().to_text()),
+          Ok(false) => println!("nothing to {step}"),
+          Err(error) => println!("{error}"),
+        }
+        if let Some(error) = history.take_snapshot_error() {
+          println!("couldn't write the snapshot: {error}");
+        }
+        continue;
+      }
+      repeat if repeat.starts_with('!') => match repeat[1..].parse::<usize>().ok().and_then(|number| history.applied().get(number.checked_sub(1)?)) {
+        Some(message) => {
+          println!("{message}");
+          message.clone()
+        }
+        None => {
+          println!("no message {} in the history", &repeat[1..]);
+          continue;
+        }
+      },
+      _ => match command::parse(&line) {
+        Ok(message) => message,
+        Err(error) => {
+          // a ^ under the spot the error is about, lined up with what was typed after the prompt
+          let column = line[..error.position].chars().count() + PROMPT.len();
+          println!("{:>width$} {}", "^", error.kind, width = column + 1);
+          continue;
+        }
+      },
+    };
+    if let Err(error) = history.apply(message) {
+      println!("{error}");
+    }
+    // the step still happened, it'll just take longer to open the log next time
+    if let Some(error) = history.take_snapshot_error() {
+      println!("couldn't write the snapshot: {error}");
+    }
+  }
+  print!("{}", history.canvas().to_text());
+}
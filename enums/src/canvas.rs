@@ -0,0 +1,211 @@
+/*
+  Something for Message to act on: a grid of character cells with a cursor and a pen colour.
+    Move { x, y }       puts the cursor at column x, row y (0, 0 is the top left)
+    Write(text)         stamps the text at the cursor going right, in the pen colour. The cursor
+                        stays where it is, a \n carries on one row down from the cursor's column,
+                        a space clears the cell under it and anything off the canvas is dropped
+    ChangeColor(color)  sets the pen
+    Quit                ends processing, call does nothing on a canvas after that
+  to_text and to_ppm draw the result, as the characters or as a picture with one pixel per cell.
+*/
+use std::fmt;
+
+use crate::color::Rgb;
+use crate::Message;
+
+const BACKGROUND: Rgb = Rgb::WHITE;
+
+// the most cells a canvas can have, anything bigger is an error rather than a huge (or overflowing) allocation
+pub const MAX_CELLS: usize = 1 << 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanvasError {
+  // width * height is over MAX_CELLS, or doesn't even fit in a usize (a zero side counts as 1)
+  TooBig { width: usize, height: usize },
+}
+
+impl fmt::Display for CanvasError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      CanvasError::TooBig { width, height } => write!(f, "a {width}x{height} canvas is over the {MAX_CELLS} cell limit"),
+    }
+  }
+}
+
+impl std::error::Error for CanvasError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+  pub glyph: char,
+  pub color: Rgb,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+  width: usize,
+  height: usize,
+  // row by row, None is an empty cell
+  cells: Vec<Option<Cell>>,
+  cursor: (i32, i32),
+  pen: Rgb,
+  finished: bool,
+}
+
+impl Canvas {
+  // an empty canvas with the cursor at the top left and a black pen
+  pub fn new(width: usize, height: usize) -> Result<Self, CanvasError> {
+    // a zero side counts as 1 so a 0 x huge canvas (no cells, but a huge to_text) is turned down too
+    let limit = width.max(1).checked_mul(height.max(1)).filter(|&cells| cells <= MAX_CELLS);
+    if limit.is_none() {
+      return Err(CanvasError::TooBig { width, height });
+    }
+    Ok(Canvas { width, height, cells: vec![None; width * height], cursor: (0, 0), pen: Rgb::BLACK, finished: false })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn cursor(&self) -> (i32, i32) {
+    self.cursor
+  }
+
+  pub fn pen(&self) -> Rgb {
+    self.pen
+  }
+
+  // true once a Quit has been applied
+  pub fn is_finished(&self) -> bool {
+    self.finished
+  }
+
+  // None when the cell is empty or off the canvas
+  pub fn cell(&self, x: i32, y: i32) -> Option<Cell> {
+    self.index(x, y).and_then(|index| self.cells[index])
+  }
+
+  fn index(&self, x: i32, y: i32) -> Option<usize> {
+    let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+    (x < self.width && y < self.height).then_some(y * self.width + x)
+  }
+
+  // applies the messages in order up to and including the first Quit, returns how many were applied
+  pub fn run<'a>(&mut self, messages: impl IntoIterator<Item = &'a Message>) -> usize {
+    let mut applied = 0;
+    for message in messages {
+      if self.finished {
+        break;
+      }
+      message.call(self);
+      applied += 1;
+    }
+    applied
+  }
+
+  fn stamp(&mut self, text: &str) {
+    let (left, mut y) = self.cursor;
+    for line in text.split('\n') {
+      // checked_add because the cursor can be anywhere an i32 can, a column or row past i32::MAX is just more off the canvas
+      let mut x = Some(left);
+      for glyph in line.chars() {
+        let Some(column) = x else {
+          break;
+        };
+        if let Some(index) = self.index(column, y) {
+          self.cells[index] = (glyph != ' ').then_some(Cell { glyph, color: self.pen });
+        }
+        x = column.checked_add(1);
+      }
+      let Some(next) = y.checked_add(1) else {
+        break;
+      };
+      y = next;
+    }
+  }
+
+  /*
+    Messages that draw this canvas again when run on a new one the same size: a ChangeColor, Move
+    and Write for each run of cells written in the same colour, then the pen and cursor put back and
+    a Quit if it had one. A glyph is never a space or a \n (stamp doesn't leave those in a cell) so
+    every run writes back exactly what's there.
+  */
+  pub fn to_messages(&self) -> Vec<Message> {
+    let mut messages = Vec::new();
+    let (mut pen, mut cursor) = (Rgb::BLACK, (0, 0));
+    let mut change_color = |messages: &mut Vec<Message>, color: Rgb| {
+      if color != pen {
+        messages.push(Message::ChangeColor(color));
+        pen = color;
+      }
+    };
+    for (y, row) in self.cells.chunks(self.width.max(1)).take(self.height).enumerate() {
+      let mut x = 0;
+      while x < row.len() {
+        let Some(first) = row[x] else {
+          x += 1;
+          continue;
+        };
+        let run: String = row[x..].iter().map_while(|cell| cell.filter(|cell| cell.color == first.color)).map(|cell| cell.glyph).collect();
+        change_color(&mut messages, first.color);
+        cursor = (x as i32, y as i32);
+        messages.push(Message::Move { x: cursor.0, y: cursor.1 });
+        x += run.chars().count();
+        messages.push(Message::Write(run));
+      }
+    }
+    change_color(&mut messages, self.pen);
+    if self.cursor != cursor {
+      messages.push(Message::Move { x: self.cursor.0, y: self.cursor.1 });
+    }
+    if self.finished {
+      messages.push(Message::Quit);
+    }
+    messages
+  }
+
+  // one line per row, . for an empty cell
+  pub fn to_text(&self) -> String {
+    let mut text = String::with_capacity((self.width + 1) * self.height);
+    for row in self.cells.chunks(self.width.max(1)).take(self.height) {
+      text.extend(row.iter().map(|cell| cell.map_or('.', |cell| cell.glyph)));
+      text.push('\n');
+    }
+    text
+  }
+
+  // a plain (P3) PPM image, one pixel per cell: the colour a cell was written in, white when it's empty
+  pub fn to_ppm(&self) -> String {
+    let mut ppm = format!("P3\n{} {}\n255\n", self.width, self.height);
+    for row in self.cells.chunks(self.width.max(1)).take(self.height) {
+      let pixels: Vec<String> = row
+        .iter()
+        .map(|cell| {
+          let Rgb(r, g, b) = cell.map_or(BACKGROUND, |cell| cell.color);
+          format!("{r} {g} {b}")
+        })
+        .collect();
+      ppm.push_str(&pixels.join("  "));
+      ppm.push('\n');
+    }
+    ppm
+  }
+}
+
+impl Message {
+  // using self to get the value that we called the method on, and match to do something different for each variant
+  pub fn call(&self, canvas: &mut Canvas) {
+    if canvas.finished {
+      return;
+    }
+    match self {
+      Message::Quit => canvas.finished = true,
+      Message::Move { x, y } => canvas.cursor = (*x, *y),
+      Message::Write(text) => canvas.stamp(text),
+      Message::ChangeColor(color) => canvas.pen = *color,
+    }
+  }
+}
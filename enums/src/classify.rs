@@ -0,0 +1,160 @@
+/*
+  What kind of address an address is: which of the special ranges set aside by the RFCs it falls in,
+  or Global when it's none of them and could be reached across the internet.
+  IPv4-mapped V6 addresses (::ffff:a.b.c.d, what a dual stack socket shows a V4 peer as) are really
+  V4 addresses, so they're classified as the V4 address they hold.
+*/
+use crate::network::{mask, to_bits};
+use crate::IpAddrKind;
+
+// how far a multicast group reaches (RFC 7346 for V6, RFC 2365 for the V4 administratively scoped ranges)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MulticastScope {
+  InterfaceLocal,
+  LinkLocal,
+  RealmLocal,
+  AdminLocal,
+  SiteLocal,
+  OrganizationLocal,
+  Global,
+  // a V6 scope value with no name (yet), the 4 bit number itself
+  Other(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressClass {
+  // 0.0.0.0 and ::, "no address"
+  Unspecified,
+  // 127.0.0.0/8 and ::1
+  Loopback,
+  // RFC 1918 (10/8, 172.16/12, 192.168/16) and V6 unique local addresses (fc00::/7)
+  Private,
+  // 169.254.0.0/16 and fe80::/10, only good on the link they were made for
+  LinkLocal,
+  Multicast(MulticastScope),
+  // set aside for examples in documentation, RFC 5737 and RFC 3849 / RFC 9637
+  Documentation,
+  // 198.18.0.0/15 and 2001:2::/48, for testing network equipment (RFC 2544 / RFC 5180)
+  Benchmarking,
+  // everything else that isn't meant to be routed: shared address space, the old class E range,
+  // broadcast, deprecated V6 site-local and IPv4-compatible addresses and so on
+  Reserved,
+  Global,
+}
+
+// (network, prefix, class) checked in order, so a smaller range has to come before one it's inside
+const RANGES: &[(IpAddrKind, u8, AddressClass)] = &[
+  (IpAddrKind::V4(0, 0, 0, 0), 32, AddressClass::Unspecified),
+  (IpAddrKind::V4(0, 0, 0, 0), 8, AddressClass::Reserved),
+  (IpAddrKind::V4(127, 0, 0, 0), 8, AddressClass::Loopback),
+  (IpAddrKind::V4(10, 0, 0, 0), 8, AddressClass::Private),
+  (IpAddrKind::V4(172, 16, 0, 0), 12, AddressClass::Private),
+  (IpAddrKind::V4(192, 168, 0, 0), 16, AddressClass::Private),
+  (IpAddrKind::V4(169, 254, 0, 0), 16, AddressClass::LinkLocal),
+  (IpAddrKind::V4(192, 0, 2, 0), 24, AddressClass::Documentation),
+  (IpAddrKind::V4(198, 51, 100, 0), 24, AddressClass::Documentation),
+  (IpAddrKind::V4(203, 0, 113, 0), 24, AddressClass::Documentation),
+  (IpAddrKind::V4(198, 18, 0, 0), 15, AddressClass::Benchmarking),
+  // carrier grade NAT (RFC 6598)
+  (IpAddrKind::V4(100, 64, 0, 0), 10, AddressClass::Reserved),
+  // IETF protocol assignments (RFC 6890)
+  (IpAddrKind::V4(192, 0, 0, 0), 24, AddressClass::Reserved),
+  // class E and the broadcast address at the end of it
+  (IpAddrKind::V4(240, 0, 0, 0), 4, AddressClass::Reserved),
+  (IpAddrKind::V6([0, 0, 0, 0, 0, 0, 0, 0]), 128, AddressClass::Unspecified),
+  (IpAddrKind::V6([0, 0, 0, 0, 0, 0, 0, 1]), 128, AddressClass::Loopback),
+  // IPv4-compatible, deprecated by RFC 4291
+  (IpAddrKind::V6([0, 0, 0, 0, 0, 0, 0, 0]), 96, AddressClass::Reserved),
+  (IpAddrKind::V6([0xfc00, 0, 0, 0, 0, 0, 0, 0]), 7, AddressClass::Private),
+  (IpAddrKind::V6([0xfe80, 0, 0, 0, 0, 0, 0, 0]), 10, AddressClass::LinkLocal),
+  // site-local, deprecated by RFC 3879
+  (IpAddrKind::V6([0xfec0, 0, 0, 0, 0, 0, 0, 0]), 10, AddressClass::Reserved),
+  (IpAddrKind::V6([0x2001, 0xdb8, 0, 0, 0, 0, 0, 0]), 32, AddressClass::Documentation),
+  (IpAddrKind::V6([0x3fff, 0, 0, 0, 0, 0, 0, 0]), 20, AddressClass::Documentation),
+  (IpAddrKind::V6([0x2001, 0x2, 0, 0, 0, 0, 0, 0]), 48, AddressClass::Benchmarking),
+  // discard only (RFC 6666)
+  (IpAddrKind::V6([0x100, 0, 0, 0, 0, 0, 0, 0]), 64, AddressClass::Reserved),
+];
+
+fn in_range(address: IpAddrKind, network: IpAddrKind, prefix: u8) -> bool {
+  let ((bits, width), (network, network_width)) = (to_bits(address), to_bits(network));
+  width == network_width && bits & mask(prefix, width) == network
+}
+
+fn v4_multicast_scope(a: u8, b: u8, c: u8) -> MulticastScope {
+  match (a, b, c) {
+    // 224.0.0.0/24, local network control, never forwarded by a router
+    (224, 0, 0) => MulticastScope::LinkLocal,
+    (239, 255, _) => MulticastScope::SiteLocal,
+    (239, 192..=195, _) => MulticastScope::OrganizationLocal,
+    (239, _, _) => MulticastScope::AdminLocal,
+    _ => MulticastScope::Global,
+  }
+}
+
+// the low 4 bits of the second byte of ffXX::
+fn v6_multicast_scope(scope: u8) -> MulticastScope {
+  match scope {
+    0x1 => MulticastScope::InterfaceLocal,
+    0x2 => MulticastScope::LinkLocal,
+    0x3 => MulticastScope::RealmLocal,
+    0x4 => MulticastScope::AdminLocal,
+    0x5 => MulticastScope::SiteLocal,
+    0x8 => MulticastScope::OrganizationLocal,
+    0xe => MulticastScope::Global,
+    other => MulticastScope::Other(other),
+  }
+}
+
+impl IpAddrKind {
+  pub fn classify(&self) -> AddressClass {
+    if let Some(v4) = self.to_ipv4_mapped() {
+      return v4.classify();
+    }
+    match *self {
+      IpAddrKind::V4(a, b, c, _) if a >> 4 == 0xe => AddressClass::Multicast(v4_multicast_scope(a, b, c)),
+      IpAddrKind::V6(segments) if segments[0] >> 8 == 0xff => {
+        AddressClass::Multicast(v6_multicast_scope((segments[0] & 0xf) as u8))
+      }
+      _ => RANGES
+        .iter()
+        .find(|&&(network, prefix, _)| in_range(*self, network, prefix))
+        .map_or(AddressClass::Global, |&(_, _, class)| class),
+    }
+  }
+
+  pub fn is_loopback(&self) -> bool {
+    self.classify() == AddressClass::Loopback
+  }
+
+  pub fn is_global(&self) -> bool {
+    self.classify() == AddressClass::Global
+  }
+
+  // the V4 address inside ::ffff:a.b.c.d
+  pub fn to_ipv4_mapped(&self) -> Option<IpAddrKind> {
+    match *self {
+      IpAddrKind::V6([0, 0, 0, 0, 0, 0xffff, high, low]) => {
+        let ([a, b], [c, d]) = (high.to_be_bytes(), low.to_be_bytes());
+        Some(IpAddrKind::V4(a, b, c, d))
+      }
+      _ => None,
+    }
+  }
+
+  /*
+    Like to_ipv4_mapped but also takes the old IPv4-compatible form (::a.b.c.d), the same as
+    std's Ipv6Addr::to_ipv4. That includes :: and ::1 which turn into 0.0.0.0 and 0.0.0.1, so
+    the loopback address stops being loopback. A V4 address is given back as it is.
+  */
+  pub fn to_ipv4(&self) -> Option<IpAddrKind> {
+    match *self {
+      IpAddrKind::V4(..) => Some(*self),
+      IpAddrKind::V6([0, 0, 0, 0, 0, 0, high, low]) => {
+        let ([a, b], [c, d]) = (high.to_be_bytes(), low.to_be_bytes());
+        Some(IpAddrKind::V4(a, b, c, d))
+      }
+      _ => self.to_ipv4_mapped(),
+    }
+  }
+}
@@ -0,0 +1,356 @@
+/*
+  A colour as red, green and blue channels from 0 to 255, the way struct Color(i32, i32, i32) in
+  the structs chapter should have been: with u8 channels a negative or too big one can't even be
+  written down, so anything that takes an Rgb doesn't have to check. The ways in are where the
+  checking happens:
+    "#ff8800".parse()       hex, or the #f80 shorthand where each digit is doubled
+    "tomato".parse()        any of the 148 CSS named colours, upper or lower case
+    Rgb::try_from((r, g, b)) i32s, an error if one is out of range
+    Hsl and Hsv             with From both ways
+  Display writes it back out as #rrggbb.
+
+  blend mixes a colour over another with some transparency and contrast_ratio is the WCAG one, for
+  checking text will be readable on its background.
+*/
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+// what WCAG asks of normal sized text against its background, AA and the stricter AAA
+pub const MIN_CONTRAST_AA: f64 = 4.5;
+pub const MIN_CONTRAST_AAA: f64 = 7.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorError {
+  // not 3 or 6 hex digits after the #
+  InvalidHex(String),
+  UnknownName(String),
+  OutOfRange(i32),
+}
+
+impl fmt::Display for ColorError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ColorError::InvalidHex(text) => write!(f, "{text:?} isn't a colour, expected #rgb or #rrggbb"),
+      ColorError::UnknownName(name) => write!(f, "there is no colour called {name:?}"),
+      ColorError::OutOfRange(value) => write!(f, "{value} is outside 0 to 255"),
+    }
+  }
+}
+
+impl std::error::Error for ColorError {}
+
+// hue in degrees from 0 up to 360, the others from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+  pub hue: f64,
+  pub saturation: f64,
+  pub lightness: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+  pub hue: f64,
+  pub saturation: f64,
+  pub value: f64,
+}
+
+// sorted by name for the binary search, where two names share a colour the first one is what Rgb::name gives
+const NAMED: [(&str, Rgb); 148] = [
+  ("aliceblue", Rgb(0xf0, 0xf8, 0xff)),
+  ("antiquewhite", Rgb(0xfa, 0xeb, 0xd7)),
+  ("aqua", Rgb(0x00, 0xff, 0xff)),
+  ("aquamarine", Rgb(0x7f, 0xff, 0xd4)),
+  ("azure", Rgb(0xf0, 0xff, 0xff)),
+  ("beige", Rgb(0xf5, 0xf5, 0xdc)),
+  ("bisque", Rgb(0xff, 0xe4, 0xc4)),
+  ("black", Rgb(0x00, 0x00, 0x00)),
+  ("blanchedalmond", Rgb(0xff, 0xeb, 0xcd)),
+  ("blue", Rgb(0x00, 0x00, 0xff)),
+  ("blueviolet", Rgb(0x8a, 0x2b, 0xe2)),
+  ("brown", Rgb(0xa5, 0x2a, 0x2a)),
+  ("burlywood", Rgb(0xde, 0xb8, 0x87)),
+  ("cadetblue", Rgb(0x5f, 0x9e, 0xa0)),
+  ("chartreuse", Rgb(0x7f, 0xff, 0x00)),
+  ("chocolate", Rgb(0xd2, 0x69, 0x1e)),
+  ("coral", Rgb(0xff, 0x7f, 0x50)),
+  ("cornflowerblue", Rgb(0x64, 0x95, 0xed)),
+  ("cornsilk", Rgb(0xff, 0xf8, 0xdc)),
+  ("crimson", Rgb(0xdc, 0x14, 0x3c)),
+  ("cyan", Rgb(0x00, 0xff, 0xff)),
+  ("darkblue", Rgb(0x00, 0x00, 0x8b)),
+  ("darkcyan", Rgb(0x00, 0x8b, 0x8b)),
+  ("darkgoldenrod", Rgb(0xb8, 0x86, 0x0b)),
+  ("darkgray", Rgb(0xa9, 0xa9, 0xa9)),
+  ("darkgreen", Rgb(0x00, 0x64, 0x00)),
+  ("darkgrey", Rgb(0xa9, 0xa9, 0xa9)),
+  ("darkkhaki", Rgb(0xbd, 0xb7, 0x6b)),
+  ("darkmagenta", Rgb(0x8b, 0x00, 0x8b)),
+  ("darkolivegreen", Rgb(0x55, 0x6b, 0x2f)),
+  ("darkorange", Rgb(0xff, 0x8c, 0x00)),
+  ("darkorchid", Rgb(0x99, 0x32, 0xcc)),
+  ("darkred", Rgb(0x8b, 0x00, 0x00)),
+  ("darksalmon", Rgb(0xe9, 0x96, 0x7a)),
+  ("darkseagreen", Rgb(0x8f, 0xbc, 0x8f)),
+  ("darkslateblue", Rgb(0x48, 0x3d, 0x8b)),
+  ("darkslategray", Rgb(0x2f, 0x4f, 0x4f)),
+  ("darkslategrey", Rgb(0x2f, 0x4f, 0x4f)),
+  ("darkturquoise", Rgb(0x00, 0xce, 0xd1)),
+  ("darkviolet", Rgb(0x94, 0x00, 0xd3)),
+  ("deeppink", Rgb(0xff, 0x14, 0x93)),
+  ("deepskyblue", Rgb(0x00, 0xbf, 0xff)),
+  ("dimgray", Rgb(0x69, 0x69, 0x69)),
+  ("dimgrey", Rgb(0x69, 0x69, 0x69)),
+  ("dodgerblue", Rgb(0x1e, 0x90, 0xff)),
+  ("firebrick", Rgb(0xb2, 0x22, 0x22)),
+  ("floralwhite", Rgb(0xff, 0xfa, 0xf0)),
+  ("forestgreen", Rgb(0x22, 0x8b, 0x22)),
+  ("fuchsia", Rgb(0xff, 0x00, 0xff)),
+  ("gainsboro", Rgb(0xdc, 0xdc, 0xdc)),
+  ("ghostwhite", Rgb(0xf8, 0xf8, 0xff)),
+  ("gold", Rgb(0xff, 0xd7, 0x00)),
+  ("goldenrod", Rgb(0xda, 0xa5, 0x20)),
+  ("gray", Rgb(0x80, 0x80, 0x80)),
+  ("green", Rgb(0x00, 0x80, 0x00)),
+  ("greenyellow", Rgb(0xad, 0xff, 0x2f)),
+  ("grey", Rgb(0x80, 0x80, 0x80)),
+  ("honeydew", Rgb(0xf0, 0xff, 0xf0)),
+  ("hotpink", Rgb(0xff, 0x69, 0xb4)),
+  ("indianred", Rgb(0xcd, 0x5c, 0x5c)),
+  ("indigo", Rgb(0x4b, 0x00, 0x82)),
+  ("ivory", Rgb(0xff, 0xff, 0xf0)),
+  ("khaki", Rgb(0xf0, 0xe6, 0x8c)),
+  ("lavender", Rgb(0xe6, 0xe6, 0xfa)),
+  ("lavenderblush", Rgb(0xff, 0xf0, 0xf5)),
+  ("lawngreen", Rgb(0x7c, 0xfc, 0x00)),
+  ("lemonchiffon", Rgb(0xff, 0xfa, 0xcd)),
+  ("lightblue", Rgb(0xad, 0xd8, 0xe6)),
+  ("lightcoral", Rgb(0xf0, 0x80, 0x80)),
+  ("lightcyan", Rgb(0xe0, 0xff, 0xff)),
+  ("lightgoldenrodyellow", Rgb(0xfa, 0xfa, 0xd2)),
+  ("lightgray", Rgb(0xd3, 0xd3, 0xd3)),
+  ("lightgreen", Rgb(0x90, 0xee, 0x90)),
+  ("lightgrey", Rgb(0xd3, 0xd3, 0xd3)),
+  ("lightpink", Rgb(0xff, 0xb6, 0xc1)),
+  ("lightsalmon", Rgb(0xff, 0xa0, 0x7a)),
+  ("lightseagreen", Rgb(0x20, 0xb2, 0xaa)),
+  ("lightskyblue", Rgb(0x87, 0xce, 0xfa)),
+  ("lightslategray", Rgb(0x77, 0x88, 0x99)),
+  ("lightslategrey", Rgb(0x77, 0x88, 0x99)),
+  ("lightsteelblue", Rgb(0xb0, 0xc4, 0xde)),
+  ("lightyellow", Rgb(0xff, 0xff, 0xe0)),
+  ("lime", Rgb(0x00, 0xff, 0x00)),
+  ("limegreen", Rgb(0x32, 0xcd, 0x32)),
+  ("linen", Rgb(0xfa, 0xf0, 0xe6)),
+  ("magenta", Rgb(0xff, 0x00, 0xff)),
+  ("maroon", Rgb(0x80, 0x00, 0x00)),
+  ("mediumaquamarine", Rgb(0x66, 0xcd, 0xaa)),
+  ("mediumblue", Rgb(0x00, 0x00, 0xcd)),
+  ("mediumorchid", Rgb(0xba, 0x55, 0xd3)),
+  ("mediumpurple", Rgb(0x93, 0x70, 0xdb)),
+  ("mediumseagreen", Rgb(0x3c, 0xb3, 0x71)),
+  ("mediumslateblue", Rgb(0x7b, 0x68, 0xee)),
+  ("mediumspringgreen", Rgb(0x00, 0xfa, 0x9a)),
+  ("mediumturquoise", Rgb(0x48, 0xd1, 0xcc)),
+  ("mediumvioletred", Rgb(0xc7, 0x15, 0x85)),
+  ("midnightblue", Rgb(0x19, 0x19, 0x70)),
+  ("mintcream", Rgb(0xf5, 0xff, 0xfa)),
+  ("mistyrose", Rgb(0xff, 0xe4, 0xe1)),
+  ("moccasin", Rgb(0xff, 0xe4, 0xb5)),
+  ("navajowhite", Rgb(0xff, 0xde, 0xad)),
+  ("navy", Rgb(0x00, 0x00, 0x80)),
+  ("oldlace", Rgb(0xfd, 0xf5, 0xe6)),
+  ("olive", Rgb(0x80, 0x80, 0x00)),
+  ("olivedrab", Rgb(0x6b, 0x8e, 0x23)),
+  ("orange", Rgb(0xff, 0xa5, 0x00)),
+  ("orangered", Rgb(0xff, 0x45, 0x00)),
+  ("orchid", Rgb(0xda, 0x70, 0xd6)),
+  ("palegoldenrod", Rgb(0xee, 0xe8, 0xaa)),
+  ("palegreen", Rgb(0x98, 0xfb, 0x98)),
+  ("paleturquoise", Rgb(0xaf, 0xee, 0xee)),
+  ("palevioletred", Rgb(0xdb, 0x70, 0x93)),
+  ("papayawhip", Rgb(0xff, 0xef, 0xd5)),
+  ("peachpuff", Rgb(0xff, 0xda, 0xb9)),
+  ("peru", Rgb(0xcd, 0x85, 0x3f)),
+  ("pink", Rgb(0xff, 0xc0, 0xcb)),
+  ("plum", Rgb(0xdd, 0xa0, 0xdd)),
+  ("powderblue", Rgb(0xb0, 0xe0, 0xe6)),
+  ("purple", Rgb(0x80, 0x00, 0x80)),
+  ("rebeccapurple", Rgb(0x66, 0x33, 0x99)),
+  ("red", Rgb(0xff, 0x00, 0x00)),
+  ("rosybrown", Rgb(0xbc, 0x8f, 0x8f)),
+  ("royalblue", Rgb(0x41, 0x69, 0xe1)),
+  ("saddlebrown", Rgb(0x8b, 0x45, 0x13)),
+  ("salmon", Rgb(0xfa, 0x80, 0x72)),
+  ("sandybrown", Rgb(0xf4, 0xa4, 0x60)),
+  ("seagreen", Rgb(0x2e, 0x8b, 0x57)),
+  ("seashell", Rgb(0xff, 0xf5, 0xee)),
+  ("sienna", Rgb(0xa0, 0x52, 0x2d)),
+  ("silver", Rgb(0xc0, 0xc0, 0xc0)),
+  ("skyblue", Rgb(0x87, 0xce, 0xeb)),
+  ("slateblue", Rgb(0x6a, 0x5a, 0xcd)),
+  ("slategray", Rgb(0x70, 0x80, 0x90)),
+  ("slategrey", Rgb(0x70, 0x80, 0x90)),
+  ("snow", Rgb(0xff, 0xfa, 0xfa)),
+  ("springgreen", Rgb(0x00, 0xff, 0x7f)),
+  ("steelblue", Rgb(0x46, 0x82, 0xb4)),
+  ("tan", Rgb(0xd2, 0xb4, 0x8c)),
+  ("teal", Rgb(0x00, 0x80, 0x80)),
+  ("thistle", Rgb(0xd8, 0xbf, 0xd8)),
+  ("tomato", Rgb(0xff, 0x63, 0x47)),
+  ("turquoise", Rgb(0x40, 0xe0, 0xd0)),
+  ("violet", Rgb(0xee, 0x82, 0xee)),
+  ("wheat", Rgb(0xf5, 0xde, 0xb3)),
+  ("white", Rgb(0xff, 0xff, 0xff)),
+  ("whitesmoke", Rgb(0xf5, 0xf5, 0xf5)),
+  ("yellow", Rgb(0xff, 0xff, 0x00)),
+  ("yellowgreen", Rgb(0x9a, 0xcd, 0x32)),
+];
+
+impl Rgb {
+  pub const BLACK: Rgb = Rgb(0, 0, 0);
+  pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+  pub fn from_name(name: &str) -> Option<Self> {
+    let name = name.to_ascii_lowercase();
+    NAMED.binary_search_by_key(&name.as_str(), |&(named, _)| named).ok().map(|index| NAMED[index].1)
+  }
+
+  // the CSS name if it has one
+  pub fn name(self) -> Option<&'static str> {
+    NAMED.iter().find(|&&(_, color)| color == self).map(|&(name, _)| name)
+  }
+
+  // the channels as 0 to 1 and the biggest and smallest of them, which HSL and HSV both start from
+  fn unit(self) -> ([f64; 3], f64, f64) {
+    let channels = [self.0, self.1, self.2].map(|channel| f64::from(channel) / 255.0);
+    let max = channels.iter().copied().fold(0.0, f64::max);
+    let min = channels.iter().copied().fold(1.0, f64::min);
+    (channels, max, min)
+  }
+
+  fn hue(self) -> f64 {
+    let ([r, g, b], max, min) = self.unit();
+    let chroma = max - min;
+    if chroma == 0.0 {
+      0.0
+    } else if max == r {
+      60.0 * ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+      60.0 * ((b - r) / chroma + 2.0)
+    } else {
+      60.0 * ((r - g) / chroma + 4.0)
+    }
+  }
+
+  // back from hue and chroma (both 0 to 1 scale apart from the hue) plus what to add to every channel
+  fn from_hue(hue: f64, chroma: f64, add: f64) -> Self {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+      0 => (chroma, x, 0.0),
+      1 => (x, chroma, 0.0),
+      2 => (0.0, chroma, x),
+      3 => (0.0, x, chroma),
+      4 => (x, 0.0, chroma),
+      _ => (chroma, 0.0, x),
+    };
+    let channel = |value: f64| ((value + add) * 255.0).round().clamp(0.0, 255.0) as u8;
+    Rgb(channel(r), channel(g), channel(b))
+  }
+
+  /*
+    self drawn over background with an opacity of alpha, 0 is just the background and 1 just self.
+    Mixed channel by channel on the 0 to 255 values like CSS does, not in linear light.
+  */
+  pub fn blend(self, background: Rgb, alpha: f64) -> Rgb {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let mix = |front: u8, back: u8| (f64::from(front) * alpha + f64::from(back) * (1.0 - alpha)).round() as u8;
+    Rgb(mix(self.0, background.0), mix(self.1, background.1), mix(self.2, background.2))
+  }
+
+  // how bright it looks from 0 (black) to 1 (white), with the sRGB curve taken off each channel first
+  pub fn relative_luminance(self) -> f64 {
+    let ([r, g, b], _, _) = self.unit();
+    let linear = |channel: f64| if channel <= 0.04045 { channel / 12.92 } else { ((channel + 0.055) / 1.055).powf(2.4) };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+  }
+
+  // from 1 (the same) to 21 (black and white), the same whichever way round the two are
+  pub fn contrast_ratio(self, other: Rgb) -> f64 {
+    let (a, b) = (self.relative_luminance(), other.relative_luminance());
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+  }
+}
+
+impl From<Rgb> for Hsl {
+  fn from(color: Rgb) -> Self {
+    let (_, max, min) = color.unit();
+    let lightness = (max + min) / 2.0;
+    let saturation = if max == min { 0.0 } else { (max - min) / (1.0 - (2.0 * lightness - 1.0).abs()) };
+    Hsl { hue: color.hue(), saturation, lightness }
+  }
+}
+
+// any hue works (it wraps round), saturation and lightness are clamped to 0 to 1
+impl From<Hsl> for Rgb {
+  fn from(hsl: Hsl) -> Self {
+    let (saturation, lightness) = (hsl.saturation.clamp(0.0, 1.0), hsl.lightness.clamp(0.0, 1.0));
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    Rgb::from_hue(hsl.hue, chroma, lightness - chroma / 2.0)
+  }
+}
+
+impl From<Rgb> for Hsv {
+  fn from(color: Rgb) -> Self {
+    let (_, max, min) = color.unit();
+    let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+    Hsv { hue: color.hue(), saturation, value: max }
+  }
+}
+
+impl From<Hsv> for Rgb {
+  fn from(hsv: Hsv) -> Self {
+    let (saturation, value) = (hsv.saturation.clamp(0.0, 1.0), hsv.value.clamp(0.0, 1.0));
+    let chroma = value * saturation;
+    Rgb::from_hue(hsv.hue, chroma, value - chroma)
+  }
+}
+
+impl TryFrom<(i32, i32, i32)> for Rgb {
+  type Error = ColorError;
+
+  fn try_from((r, g, b): (i32, i32, i32)) -> Result<Self, ColorError> {
+    let channel = |value: i32| u8::try_from(value).map_err(|_| ColorError::OutOfRange(value));
+    Ok(Rgb(channel(r)?, channel(g)?, channel(b)?))
+  }
+}
+
+impl FromStr for Rgb {
+  type Err = ColorError;
+
+  fn from_str(text: &str) -> Result<Self, ColorError> {
+    let Some(hex) = text.strip_prefix('#') else {
+      return Rgb::from_name(text).ok_or_else(|| ColorError::UnknownName(text.to_string()));
+    };
+    let invalid = || ColorError::InvalidHex(text.to_string());
+    // from_str_radix would let a + through
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+      return Err(invalid());
+    }
+    let digit = |index: usize| u8::from_str_radix(&hex[index..index + 1], 16).unwrap();
+    match hex.len() {
+      3 => Ok(Rgb(digit(0) * 17, digit(1) * 17, digit(2) * 17)),
+      6 => Ok(Rgb(digit(0) << 4 | digit(1), digit(2) << 4 | digit(3), digit(4) << 4 | digit(5))),
+      _ => Err(invalid()),
+    }
+  }
+}
+
+impl fmt::Display for Rgb {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+  }
+}
@@ -0,0 +1,190 @@
+/*
+  Messages typed out as commands, one per line:
+    move 3 4
+    write "hello"
+    color 255 0 0       or color #ff0000, color #f00, color red
+    quit
+  The line is cut into tokens first (words, whole numbers and "quoted strings"), then the first
+  word picks the variant and the rest have to be the arguments it takes. Errors carry the byte
+  position in the line they're about so the REPL can point at it.
+  Display on Message writes the same syntax back out, so parse(&message.to_string()) gives the
+  message back.
+*/
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::color::{ColorError, Rgb};
+use crate::Message;
+
+// the syntax of every variant, for the REPL's help
+pub const HELP: &str = "\
+move X Y          Message::Move { x: X, y: Y }, whole numbers
+write \"TEXT\"      Message::Write(TEXT), \\\" \\\\ and \\n work inside the quotes
+color R G B       Message::ChangeColor(Rgb(R, G, B)), whole numbers from 0 to 255
+color #RRGGBB     the same from hex (or #RGB), or a CSS colour name like color tomato
+quit              Message::Quit";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+  Empty,
+  UnknownCommand(String),
+  // a " with no closing one, the position is the opening one
+  UnterminatedString,
+  // a \ followed by something other than " \ or n
+  InvalidEscape(char),
+  // a number too big (or small) for an i32
+  NumberOutOfRange,
+  // what was expected, the argument's name for the help text
+  Expected(&'static str),
+  TooManyArguments,
+  // a word after color that isn't a hex colour or a colour name
+  Color(ColorError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  pub position: usize,
+  pub kind: ErrorKind,
+}
+
+impl fmt::Display for ErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ErrorKind::Empty => write!(f, "expected a command"),
+      ErrorKind::UnknownCommand(word) => write!(f, "unknown command {word:?}, try help"),
+      ErrorKind::UnterminatedString => write!(f, "this string has no closing quote"),
+      ErrorKind::InvalidEscape(c) => write!(f, "unknown escape \\{c}"),
+      ErrorKind::NumberOutOfRange => write!(f, "number doesn't fit in an i32"),
+      ErrorKind::Expected(what) => write!(f, "expected {what}"),
+      ErrorKind::TooManyArguments => write!(f, "too many arguments"),
+      ErrorKind::Color(error) => write!(f, "{error}"),
+    }
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "position {}: {}", self.position, self.kind)
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+  Word(String),
+  Number(i32),
+  Str(String),
+}
+
+// every token with the byte position it starts at
+pub fn tokenize(line: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+  let mut tokens = Vec::new();
+  let mut chars = line.char_indices().peekable();
+  while let Some(&(start, c)) = chars.peek() {
+    if c.is_whitespace() {
+      chars.next();
+    } else if c == '"' {
+      chars.next();
+      let mut text = String::new();
+      loop {
+        match chars.next() {
+          None => return Err(ParseError { position: start, kind: ErrorKind::UnterminatedString }),
+          Some((_, '"')) => break,
+          Some((escape, '\\')) => match chars.next() {
+            Some((_, '"')) => text.push('"'),
+            Some((_, '\\')) => text.push('\\'),
+            Some((_, 'n')) => text.push('\n'),
+            Some((_, other)) => return Err(ParseError { position: escape, kind: ErrorKind::InvalidEscape(other) }),
+            None => return Err(ParseError { position: start, kind: ErrorKind::UnterminatedString }),
+          },
+          Some((_, c)) => text.push(c),
+        }
+      }
+      tokens.push((start, Token::Str(text)));
+    } else {
+      let mut word = String::new();
+      while let Some(&(_, c)) = chars.peek() {
+        if c.is_whitespace() || c == '"' {
+          break;
+        }
+        word.push(c);
+        chars.next();
+      }
+      let digits = word.strip_prefix('-').unwrap_or(&word);
+      let token = if !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        Token::Number(word.parse().map_err(|_| ParseError { position: start, kind: ErrorKind::NumberOutOfRange })?)
+      } else {
+        Token::Word(word)
+      };
+      tokens.push((start, token));
+    }
+  }
+  Ok(tokens)
+}
+
+// the next token as a number in range, end is where the line finishes for when there isn't one
+fn number(tokens: &mut impl Iterator<Item = (usize, Token)>, end: usize, name: &'static str, range: RangeInclusive<i32>) -> Result<i32, ParseError> {
+  match tokens.next() {
+    Some((_, Token::Number(value))) if range.contains(&value) => Ok(value),
+    Some((position, _)) => Err(ParseError { position, kind: ErrorKind::Expected(name) }),
+    None => Err(ParseError { position: end, kind: ErrorKind::Expected(name) }),
+  }
+}
+
+pub fn parse(line: &str) -> Result<Message, ParseError> {
+  let tokens = tokenize(line)?;
+  let mut tokens = tokens.into_iter().peekable();
+  let (position, command) = match tokens.next() {
+    Some((position, Token::Word(word))) => (position, word.to_lowercase()),
+    Some((position, _)) => return Err(ParseError { position, kind: ErrorKind::Expected("a command") }),
+    None => return Err(ParseError { position: line.len(), kind: ErrorKind::Empty }),
+  };
+
+  let end = line.len();
+  let message = match command.as_str() {
+    "quit" => Message::Quit,
+    "move" => Message::Move {
+      x: number(&mut tokens, end, "X, a whole number", i32::MIN..=i32::MAX)?,
+      y: number(&mut tokens, end, "Y, a whole number", i32::MIN..=i32::MAX)?,
+    },
+    "color" | "colour" => match tokens.next_if(|(_, token)| matches!(token, Token::Word(_))) {
+      Some((position, Token::Word(word))) => {
+        Message::ChangeColor(word.parse().map_err(|error| ParseError { position, kind: ErrorKind::Color(error) })?)
+      }
+      _ => {
+        // the range check means the casts can't cut anything off
+        let mut channel = |name| number(&mut tokens, end, name, 0..=255).map(|value| value as u8);
+        Message::ChangeColor(Rgb(
+          channel("R, a whole number from 0 to 255")?,
+          channel("G, a whole number from 0 to 255")?,
+          channel("B, a whole number from 0 to 255")?,
+        ))
+      }
+    },
+    "write" => match tokens.next() {
+      Some((_, Token::Str(text))) => Message::Write(text),
+      Some((position, _)) => return Err(ParseError { position, kind: ErrorKind::Expected("\"TEXT\" in quotes") }),
+      None => return Err(ParseError { position: end, kind: ErrorKind::Expected("\"TEXT\" in quotes") }),
+    },
+    _ => return Err(ParseError { position, kind: ErrorKind::UnknownCommand(command) }),
+  };
+  match tokens.next() {
+    Some((position, _)) => Err(ParseError { position, kind: ErrorKind::TooManyArguments }),
+    None => Ok(message),
+  }
+}
+
+impl fmt::Display for Message {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Message::Quit => write!(f, "quit"),
+      Message::Move { x, y } => write!(f, "move {x} {y}"),
+      Message::Write(text) => {
+        let escaped = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+        write!(f, "write \"{escaped}\"")
+      }
+      Message::ChangeColor(Rgb(r, g, b)) => write!(f, "color {r} {g} {b}"),
+    }
+  }
+}
@@ -0,0 +1,355 @@
+/*
+  Every message applied to a canvas kept in order, so the canvas can be rebuilt from them, stepped
+  back and forward, and survive the program stopping halfway. The log is a text file written in the
+  command syntax, one entry a line, and only ever added to:
+    canvas 40 10        the size, always the first line
+    move 3 4            a message that was applied
+    write "hi"
+    undo                the last message applied was taken back
+    redo                and then put back again
+  Replaying the entries gives the stack of messages that are applied right now, and the canvas is
+  what you get running that stack on a new one. Undo doesn't work out an inverse message, there
+  isn't always one: a Write throws away whatever the cells held before and nothing un-Quits a
+  canvas. It goes back to the closest snapshot at or before the step it wants and runs the messages
+  after that instead.
+
+  Snapshots are copies of the canvas taken every so many messages deep into the stack, kept in
+  memory, and every so many log entries the current canvas goes to a second file next to the log
+  (log path + ".snapshot") as its first line `snapshot ENTRIES DEPTH` then the messages from
+  Canvas::to_messages. Opening a long log starts from that instead of running every message again.
+  The snapshot file is only ever a shortcut, so not being able to write it doesn't stop anything,
+  take_snapshot_error hands out what went wrong.
+*/
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::canvas::{Canvas, CanvasError};
+use crate::command;
+use crate::Message;
+
+// how many messages apart the snapshots are, and how many log entries apart the snapshot file is written
+const SNAPSHOT_EVERY: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+  Apply(Message),
+  Undo,
+  Redo,
+}
+
+impl fmt::Display for Entry {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Entry::Apply(message) => write!(f, "{message}"),
+      Entry::Undo => write!(f, "undo"),
+      Entry::Redo => write!(f, "redo"),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+  Io(io::Error),
+  Canvas(CanvasError),
+  // line numbers start at 1
+  Log { line: usize, message: String },
+  Snapshot { line: usize, message: String },
+}
+
+impl fmt::Display for HistoryError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      HistoryError::Io(error) => write!(f, "{error}"),
+      HistoryError::Canvas(error) => write!(f, "{error}"),
+      HistoryError::Log { line, message } => write!(f, "log line {line}: {message}"),
+      HistoryError::Snapshot { line, message } => write!(f, "snapshot line {line}: {message}"),
+    }
+  }
+}
+
+impl std::error::Error for HistoryError {}
+
+impl From<io::Error> for HistoryError {
+  fn from(error: io::Error) -> Self {
+    HistoryError::Io(error)
+  }
+}
+
+impl From<CanvasError> for HistoryError {
+  fn from(error: CanvasError) -> Self {
+    HistoryError::Canvas(error)
+  }
+}
+
+fn parse_entry(line: &str) -> Result<Entry, command::ParseError> {
+  match line.trim() {
+    "undo" => Ok(Entry::Undo),
+    "redo" => Ok(Entry::Redo),
+    _ => command::parse(line).map(Entry::Apply),
+  }
+}
+
+pub struct History {
+  width: usize,
+  height: usize,
+  entries: Vec<Entry>,
+  // the messages applied right now, and the ones undo took off that redo can put back (last undone last)
+  applied: Vec<Message>,
+  undone: Vec<Message>,
+  // (depth, the canvas after applied[..depth]) sorted by depth, always at least the empty canvas at 0
+  snapshots: Vec<(usize, Canvas)>,
+  snapshot_every: usize,
+  canvas: Canvas,
+  log: Option<(File, PathBuf)>,
+  // why the last snapshot file couldn't be written, until take_snapshot_error takes it
+  snapshot_error: Option<HistoryError>,
+}
+
+impl History {
+  pub fn new(width: usize, height: usize) -> Result<Self, HistoryError> {
+    Ok(History::starting_from(Canvas::new(width, height)?))
+  }
+
+  // nothing has happened yet, canvas is the blank one everything starts from
+  fn starting_from(canvas: Canvas) -> Self {
+    History {
+      width: canvas.width(),
+      height: canvas.height(),
+      entries: Vec::new(),
+      applied: Vec::new(),
+      undone: Vec::new(),
+      snapshots: vec![(0, canvas.clone())],
+      snapshot_every: SNAPSHOT_EVERY,
+      canvas,
+      log: None,
+      snapshot_error: None,
+    }
+  }
+
+  // at least 1, fewer means faster undo and more memory
+  pub fn with_snapshot_every(mut self, messages: usize) -> Self {
+    self.snapshot_every = messages.max(1);
+    self
+  }
+
+  /*
+    Keeps the history in the log file at path, replaying what's already in it first. A last line
+    with no \n on the end is a write that was cut off, so it's dropped (from the file too) rather
+    than being an error. The log has to be for a canvas the same size as this one.
+  */
+  pub fn with_log(mut self, path: impl AsRef<Path>) -> Result<Self, HistoryError> {
+    let path = path.as_ref().to_path_buf();
+    let mut file = OpenOptions::new().read(true).append(true).create(true).open(&path)?;
+    let mut text = fs::read_to_string(&path)?;
+    if !text.ends_with('\n') {
+      text.truncate(text.rfind('\n').map_or(0, |end| end + 1));
+      file.set_len(text.len() as u64)?;
+    }
+    if text.is_empty() {
+      file.write_all(format!("canvas {} {}\n", self.width, self.height).as_bytes())?;
+    } else {
+      self.load(&text, &path)?;
+    }
+    self.log = Some((file, path));
+    Ok(self)
+  }
+
+  fn load(&mut self, text: &str, path: &Path) -> Result<(), HistoryError> {
+    let mut lines = text.lines();
+    let size = format!("canvas {} {}", self.width, self.height);
+    let header = lines.next().unwrap_or_default();
+    if header != size {
+      return Err(HistoryError::Log { line: 1, message: format!("expected {size:?}, found {header:?}") });
+    }
+    let entries = lines
+      .enumerate()
+      .map(|(index, line)| parse_entry(line).map_err(|error| HistoryError::Log { line: index + 2, message: error.to_string() }))
+      .collect::<Result<Vec<Entry>, HistoryError>>()?;
+
+    // a snapshot from further along than the log goes is from a log that lost its end, so it's no use
+    let mut snapshot = self.read_snapshot(path)?.filter(|&(at, _, _)| at <= entries.len());
+    let mut entries = entries.into_iter();
+    loop {
+      let here = (self.entries.len(), self.applied.len());
+      if let Some((_, depth, canvas)) = snapshot.take_if(|(at, depth, _)| (*at, *depth) == here) {
+        self.keep_snapshot(depth, canvas);
+      }
+      let Some(entry) = entries.next() else {
+        break;
+      };
+      self.record(entry);
+    }
+    self.canvas = self.rebuild(self.applied.len());
+    Ok(())
+  }
+
+  fn snapshot_path(log: &Path) -> PathBuf {
+    let mut path = log.to_path_buf().into_os_string();
+    path.push(".snapshot");
+    path.into()
+  }
+
+  // (entries, depth, canvas), None if there isn't a snapshot file
+  fn read_snapshot(&self, log: &Path) -> Result<Option<(usize, usize, Canvas)>, HistoryError> {
+    let text = match fs::read_to_string(Self::snapshot_path(log)) {
+      Ok(text) => text,
+      Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+      Err(error) => return Err(error.into()),
+    };
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or_default();
+    let (at, depth) = match header.split_whitespace().collect::<Vec<&str>>()[..] {
+      ["snapshot", at, depth] => (at.parse().ok(), depth.parse().ok()),
+      _ => (None, None),
+    };
+    let (Some(at), Some(depth)) = (at, depth) else {
+      return Err(HistoryError::Snapshot { line: 1, message: format!("expected `snapshot ENTRIES DEPTH`, found {header:?}") });
+    };
+    let mut canvas = self.snapshots[0].1.clone();
+    for (index, line) in lines.enumerate() {
+      let message = command::parse(line).map_err(|error| HistoryError::Snapshot { line: index + 2, message: error.to_string() })?;
+      message.call(&mut canvas);
+    }
+    Ok(Some((at, depth, canvas)))
+  }
+
+  fn write_snapshot(&self) -> Result<(), HistoryError> {
+    let Some((_, log)) = &self.log else {
+      return Ok(());
+    };
+    let mut text = format!("snapshot {} {}\n", self.entries.len(), self.applied.len());
+    for message in self.canvas.to_messages() {
+      text.push_str(&format!("{message}\n"));
+    }
+    // written next to it and renamed over it, so a crash leaves the old snapshot or the new one and never half of one
+    let path = Self::snapshot_path(log);
+    let mut temporary = path.clone().into_os_string();
+    temporary.push(".tmp");
+    fs::write(&temporary, text)?;
+    fs::rename(&temporary, path)?;
+    Ok(())
+  }
+
+  fn keep_snapshot(&mut self, depth: usize, canvas: Canvas) {
+    if let Err(index) = self.snapshots.binary_search_by_key(&depth, |&(at, _)| at) {
+      self.snapshots.insert(index, (depth, canvas));
+    }
+  }
+
+  // the canvas after applied[..depth], from the closest snapshot, keeping the snapshots it passes on the way
+  fn rebuild(&mut self, depth: usize) -> Canvas {
+    let index = self.snapshots.partition_point(|&(at, _)| at <= depth) - 1;
+    let (mut at, mut canvas) = self.snapshots[index].clone();
+    while at < depth {
+      self.applied[at].call(&mut canvas);
+      at += 1;
+      if at.is_multiple_of(self.snapshot_every) {
+        self.keep_snapshot(at, canvas.clone());
+      }
+    }
+    canvas
+  }
+
+  // the entry's effect on the stacks, the canvas is left for whoever calls this to bring up to date
+  fn record(&mut self, entry: Entry) {
+    match &entry {
+      Entry::Apply(message) => {
+        // a snapshot deeper than the stack is of messages that were undone and are now gone for good
+        self.snapshots.retain(|&(at, _)| at <= self.applied.len());
+        self.applied.push(message.clone());
+        self.undone.clear();
+      }
+      Entry::Undo => self.undone.extend(self.applied.pop()),
+      Entry::Redo => self.applied.extend(self.undone.pop()),
+    }
+    self.entries.push(entry);
+  }
+
+  /*
+    The entry goes in the log before anything changes, if that fails nothing has happened. Once it's
+    in the log it has happened whatever comes after, so a snapshot file that can't be written is kept
+    for take_snapshot_error instead of failing the step (the next snapshot tries again).
+  */
+  fn append(&mut self, entry: Entry, canvas: Canvas) -> Result<(), HistoryError> {
+    if let Some((file, _)) = &mut self.log {
+      // one write for the whole line so a crash can only cut off the last one
+      file.write_all(format!("{entry}\n").as_bytes())?;
+    }
+    self.canvas = canvas;
+    self.record(entry);
+    let depth = self.applied.len();
+    if depth.is_multiple_of(self.snapshot_every) {
+      self.keep_snapshot(depth, self.canvas.clone());
+    }
+    if self.entries.len().is_multiple_of(self.snapshot_every) {
+      self.snapshot_error = self.write_snapshot().err();
+    }
+    Ok(())
+  }
+
+  // why the snapshot file couldn't be written the last time it was, None if it was (or hasn't been yet)
+  pub fn take_snapshot_error(&mut self) -> Option<HistoryError> {
+    self.snapshot_error.take()
+  }
+
+  pub fn apply(&mut self, message: Message) -> Result<(), HistoryError> {
+    let mut canvas = self.canvas.clone();
+    message.call(&mut canvas);
+    self.append(Entry::Apply(message), canvas)
+  }
+
+  // false if there was nothing to undo, which isn't logged
+  pub fn undo(&mut self) -> Result<bool, HistoryError> {
+    if self.applied.is_empty() {
+      return Ok(false);
+    }
+    let canvas = self.rebuild(self.applied.len() - 1);
+    self.append(Entry::Undo, canvas)?;
+    Ok(true)
+  }
+
+  // false if there was nothing to redo, which isn't logged
+  pub fn redo(&mut self) -> Result<bool, HistoryError> {
+    let Some(message) = self.undone.last() else {
+      return Ok(false);
+    };
+    let mut canvas = self.canvas.clone();
+    message.call(&mut canvas);
+    self.append(Entry::Redo, canvas)?;
+    Ok(true)
+  }
+
+  pub fn canvas(&self) -> &Canvas {
+    &self.canvas
+  }
+
+  // everything that has happened, undos and redos included, in the order it happened
+  pub fn entries(&self) -> &[Entry] {
+    &self.entries
+  }
+
+  // the messages that make up the canvas right now
+  pub fn applied(&self) -> &[Message] {
+    &self.applied
+  }
+
+  pub fn can_undo(&self) -> bool {
+    !self.applied.is_empty()
+  }
+
+  pub fn can_redo(&self) -> bool {
+    !self.undone.is_empty()
+  }
+
+  // the canvas as it was after the first `step` entries, None past the end
+  pub fn state_at(&self, step: usize) -> Option<Canvas> {
+    let entries = self.entries.get(..step)?;
+    let mut past = History::starting_from(self.snapshots[0].1.clone()).with_snapshot_every(self.snapshot_every);
+    for entry in entries {
+      past.record(entry.clone());
+    }
+    Some(past.rebuild(past.applied.len()))
+  }
+}
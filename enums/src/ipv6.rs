@@ -0,0 +1,215 @@
+/*
+  Reading and writing IPv6 addresses as text. Inside IpAddrKind::V6 an address is just eight u16
+  segments, but written down it has a few shortcuts:
+    - leading zeros in a segment can be left out: 0db8 is db8
+    - one run of zero segments can be squashed into `::`, so ::1 is 0:0:0:0:0:0:0:1
+    - the last two segments can be written as an IPv4 address: ::ffff:192.0.2.1
+    - a zone (which interface a link-local address is on) can follow a %: fe80::1%eth0
+  parse understands all of that. format goes the other way and always writes the one canonical
+  form RFC 5952 asks for, so the same address always prints the same text.
+*/
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+  Empty,
+  UnexpectedChar(char),
+  // a segment with more than four hex digits
+  SegmentTooLong,
+  // a `:` with nothing after it, or a single `:` at the start
+  MissingSegment,
+  // `::` can only be used once, otherwise there is no telling how many zeros each one stands for
+  SecondDoubleColon,
+  TooManySegments,
+  TooFewSegments,
+  // the dotted IPv4 part at the end isn't four numbers from 0 to 255
+  InvalidIpv4,
+  EmptyZone,
+}
+
+// position is the byte offset in the text where things went wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+  pub position: usize,
+  pub kind: ErrorKind,
+}
+
+impl fmt::Display for ErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ErrorKind::Empty => write!(f, "the address is empty"),
+      ErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+      ErrorKind::SegmentTooLong => write!(f, "a segment has more than 4 hex digits"),
+      ErrorKind::MissingSegment => write!(f, "expected a segment"),
+      ErrorKind::SecondDoubleColon => write!(f, "`::` can only appear once"),
+      ErrorKind::TooManySegments => write!(f, "more than 8 segments"),
+      ErrorKind::TooFewSegments => write!(f, "fewer than 8 segments and no `::`"),
+      ErrorKind::InvalidIpv4 => write!(f, "the IPv4 part has to be four numbers from 0 to 255"),
+      ErrorKind::EmptyZone => write!(f, "nothing after the % for the zone"),
+    }
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "invalid IPv6 address at position {}: {}", self.position, self.kind)
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+fn error(position: usize, kind: ErrorKind) -> ParseError {
+  ParseError { position, kind }
+}
+
+// the segments and, if there was a %, the zone after it
+pub fn parse(text: &str) -> Result<([u16; 8], Option<&str>), ParseError> {
+  let (address, zone) = match text.find('%') {
+    Some(percent) if percent + 1 == text.len() => return Err(error(text.len(), ErrorKind::EmptyZone)),
+    Some(percent) => (&text[..percent], Some(&text[percent + 1..])),
+    None => (text, None),
+  };
+  Ok((parse_segments(address)?, zone))
+}
+
+fn parse_segments(text: &str) -> Result<[u16; 8], ParseError> {
+  let bytes = text.as_bytes();
+  if bytes.is_empty() {
+    return Err(error(0, ErrorKind::Empty));
+  }
+
+  // the segments before the `::` and the ones after it, the gap between them is all zeros
+  let mut before = Vec::new();
+  let mut after = Vec::new();
+  let mut compressed = false;
+  let mut i = 0;
+
+  if bytes.starts_with(b"::") {
+    compressed = true;
+    i = 2;
+  } else if bytes[0] == b':' {
+    return Err(error(0, ErrorKind::MissingSegment));
+  }
+
+  while i < bytes.len() {
+    let start = i;
+    while i < bytes.len() && bytes[i].is_ascii_hexdigit() {
+      i += 1;
+    }
+
+    let segments = if compressed { &mut after } else { &mut before };
+    if bytes.get(i) == Some(&b'.') {
+      // the rest of the text is an IPv4 address filling the last two segments
+      let [a, b, c, d] = parse_ipv4(text, start)?;
+      segments.push(u16::from_be_bytes([a, b]));
+      segments.push(u16::from_be_bytes([c, d]));
+      i = bytes.len();
+    } else {
+      if i == start {
+        let kind = if i == bytes.len() || bytes[i] == b':' { ErrorKind::MissingSegment } else { unexpected(text, i) };
+        return Err(error(start, kind));
+      }
+      if i - start > 4 {
+        return Err(error(start, ErrorKind::SegmentTooLong));
+      }
+      segments.push(u16::from_str_radix(&text[start..i], 16).unwrap());
+    }
+    if before.len() + after.len() > if compressed { 7 } else { 8 } {
+      return Err(error(start, ErrorKind::TooManySegments));
+    }
+
+    if i == bytes.len() {
+      break;
+    }
+    if bytes[i] != b':' {
+      return Err(error(i, unexpected(text, i)));
+    }
+    if bytes.get(i + 1) == Some(&b':') {
+      if compressed {
+        return Err(error(i, ErrorKind::SecondDoubleColon));
+      }
+      if before.len() == 8 {
+        // there's no room left for the zero it has to stand for
+        return Err(error(i, ErrorKind::TooManySegments));
+      }
+      compressed = true;
+      i += 2;
+    } else if i + 1 == bytes.len() {
+      return Err(error(i + 1, ErrorKind::MissingSegment));
+    } else {
+      i += 1;
+    }
+  }
+
+  if !compressed && before.len() < 8 {
+    return Err(error(bytes.len(), ErrorKind::TooFewSegments));
+  }
+  let mut segments = [0; 8];
+  segments[..before.len()].copy_from_slice(&before);
+  segments[8 - after.len()..].copy_from_slice(&after);
+  Ok(segments)
+}
+
+fn unexpected(text: &str, position: usize) -> ErrorKind {
+  ErrorKind::UnexpectedChar(text[position..].chars().next().unwrap_or('\0'))
+}
+
+// dotted decimal from start to the end of text, no leading zeros (010 could mean 8 or 10 depending on who reads it)
+pub(crate) fn parse_ipv4(text: &str, start: usize) -> Result<[u8; 4], ParseError> {
+  let mut octets = [0; 4];
+  let mut position = start;
+  let mut parts = text[start..].split('.');
+  for octet in octets.iter_mut() {
+    let part = parts.next().ok_or(error(text.len(), ErrorKind::InvalidIpv4))?;
+    if let Some(bad) = part.find(|c: char| !c.is_ascii_digit()) {
+      return Err(error(position + bad, unexpected(text, position + bad)));
+    }
+    if part.is_empty() || part.len() > 3 || (part.len() > 1 && part.starts_with('0')) {
+      return Err(error(position, ErrorKind::InvalidIpv4));
+    }
+    *octet = part.parse().map_err(|_| error(position, ErrorKind::InvalidIpv4))?;
+    position += part.len() + 1;
+  }
+  if parts.next().is_some() {
+    return Err(error(position - 1, ErrorKind::InvalidIpv4));
+  }
+  Ok(octets)
+}
+
+/*
+  RFC 5952 text: lowercase hex without leading zeros, the longest run of two or more zero segments
+  (the first one if there's a tie) squashed into `::`, and an IPv4-mapped address (::ffff:a.b.c.d)
+  with its last two segments written as IPv4 since that's how everyone recognises them.
+*/
+pub fn format(segments: &[u16; 8]) -> String {
+  if let [0, 0, 0, 0, 0, 0xffff, high, low] = *segments {
+    let [a, b] = high.to_be_bytes();
+    let [c, d] = low.to_be_bytes();
+    return format!("::ffff:{a}.{b}.{c}.{d}");
+  }
+
+  // (start, length) of the longest run of zeros
+  let mut longest = (0, 0);
+  let mut run_start = 0;
+  for (i, &segment) in segments.iter().enumerate() {
+    if segment != 0 {
+      run_start = i + 1;
+    } else if i + 1 - run_start > longest.1 {
+      longest = (run_start, i + 1 - run_start);
+    }
+  }
+
+  let hex = |segments: &[u16]| segments.iter().map(|segment| format!("{segment:x}")).collect::<Vec<_>>().join(":");
+  if longest.1 < 2 {
+    return hex(segments);
+  }
+  let (start, length) = longest;
+  format!("{}::{}", hex(&segments[..start]), hex(&segments[start + length..]))
+}
+
+pub fn format_with_zone(segments: &[u16; 8], zone: Option<&str>) -> String {
+  match zone {
+    Some(zone) => format!("{}%{zone}", format(segments)),
+    None => format(segments),
+  }
+}
@@ -0,0 +1,316 @@
+/*
+  A DHCP style address pool for the lab simulator: clients ask for an address by their client id
+  and get a lease on one from the pool's network for lease_time seconds. Asking again before it runs
+  out renews it (same address, new expiry), and once it has run out the address can go to someone
+  else. Addresses can be kept out of the pool with exclude, and reserve ties an address to one
+  client so that client always gets it and nobody else ever does.
+
+  Time comes from a Clock so tests can decide what time it is: SystemClock is the real one and any
+  closure returning seconds works too (move || now.get() over a Cell in a test).
+
+  with_store keeps the leases in a file, one `client address expires` line each, read back in when
+  the pool is made again so leases survive a restart. The file is rewritten after every change by
+  writing a new one next to it and renaming it over the old one, so a crash halfway through leaves
+  either the old file or the new one and never half of each. A change only takes effect in the pool
+  once the file is written, so when saving fails the pool and the file still agree.
+*/
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fmt, fs, io};
+
+use crate::network::{from_bits, to_bits, IpNetwork};
+use crate::IpAddrKind;
+
+pub trait Clock {
+  // seconds since the unix epoch
+  fn now(&self) -> u64;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  fn now(&self) -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs())
+  }
+}
+
+impl<F: Fn() -> u64> Clock for F {
+  fn now(&self) -> u64 {
+    self()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lease {
+  pub client_id: String,
+  pub address: IpAddrKind,
+  // the lease is over at this second
+  pub expires: u64,
+}
+
+impl Lease {
+  pub fn is_expired(&self, now: u64) -> bool {
+    now >= self.expires
+  }
+}
+
+#[derive(Debug)]
+pub enum LeaseError {
+  // every address is leased, reserved or excluded
+  Exhausted,
+  NotInPool(IpAddrKind),
+  Excluded(IpAddrKind),
+  // leased or reserved to another client
+  AddressTaken(IpAddrKind),
+  // client ids go in the store file between spaces, so they can't be empty or have any whitespace
+  InvalidClientId(String),
+  Io(io::Error),
+  // line numbers start at 1
+  BadStoreLine { line: usize, message: String },
+}
+
+impl fmt::Display for LeaseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      LeaseError::Exhausted => write!(f, "no addresses left in the pool"),
+      LeaseError::NotInPool(address) => write!(f, "{address} is not in the pool's network"),
+      LeaseError::Excluded(address) => write!(f, "{address} is excluded from the pool"),
+      LeaseError::AddressTaken(address) => write!(f, "{address} belongs to another client"),
+      LeaseError::InvalidClientId(id) => write!(f, "invalid client id {id:?}, it can't be empty or contain whitespace"),
+      LeaseError::Io(error) => write!(f, "{error}"),
+      LeaseError::BadStoreLine { line, message } => write!(f, "lease file line {line}: {message}"),
+    }
+  }
+}
+
+impl std::error::Error for LeaseError {}
+
+impl From<io::Error> for LeaseError {
+  fn from(error: io::Error) -> Self {
+    LeaseError::Io(error)
+  }
+}
+
+pub struct LeasePool<C: Clock> {
+  network: IpNetwork,
+  lease_time: u64,
+  clock: C,
+  excluded: Vec<IpNetwork>,
+  reservations: HashMap<String, IpAddrKind>,
+  // by client id, and which client has each address
+  leases: HashMap<String, Lease>,
+  holders: HashMap<IpAddrKind, String>,
+  store: Option<PathBuf>,
+}
+
+fn check_client_id(client_id: &str) -> Result<(), LeaseError> {
+  if client_id.is_empty() || client_id.contains(char::is_whitespace) {
+    return Err(LeaseError::InvalidClientId(client_id.to_string()));
+  }
+  Ok(())
+}
+
+impl<C: Clock> LeasePool<C> {
+  // hands out network's hosts (so no network or broadcast address for V4), each for lease_time seconds
+  pub fn new(network: IpNetwork, lease_time: u64, clock: C) -> Self {
+    LeasePool {
+      network,
+      lease_time,
+      clock,
+      excluded: Vec::new(),
+      reservations: HashMap::new(),
+      leases: HashMap::new(),
+      holders: HashMap::new(),
+      store: None,
+    }
+  }
+
+  // keeps the leases in the file at path, loading whatever is already in it (a missing file is an empty pool)
+  pub fn with_store(mut self, path: impl AsRef<Path>) -> Result<Self, LeaseError> {
+    let path = path.as_ref().to_path_buf();
+    match fs::read_to_string(&path) {
+      Ok(text) => self.load(&text)?,
+      Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+      Err(error) => return Err(error.into()),
+    }
+    self.store = Some(path);
+    Ok(self)
+  }
+
+  // never hands out anything in this network (one address is a /32 or /128)
+  pub fn exclude(&mut self, network: IpNetwork) {
+    self.excluded.push(network);
+  }
+
+  pub fn reserve(&mut self, client_id: &str, address: IpAddrKind) -> Result<(), LeaseError> {
+    check_client_id(client_id)?;
+    self.check_address(address)?;
+    let reserved_by_other = self.reservations.iter().any(|(other, &reserved)| reserved == address && other != client_id);
+    let leased_to_other = self.holders.get(&address).is_some_and(|holder| holder != client_id);
+    if reserved_by_other || leased_to_other {
+      return Err(LeaseError::AddressTaken(address));
+    }
+    self.reservations.insert(client_id.to_string(), address);
+    Ok(())
+  }
+
+  fn check_address(&self, address: IpAddrKind) -> Result<(), LeaseError> {
+    if !self.is_host(address) {
+      return Err(LeaseError::NotInPool(address));
+    }
+    if self.excluded.iter().any(|excluded| excluded.contains(address)) {
+      return Err(LeaseError::Excluded(address));
+    }
+    Ok(())
+  }
+
+  // one of the addresses hosts() hands out: in the network, and not the V4 network or broadcast address if it has them
+  fn is_host(&self, address: IpAddrKind) -> bool {
+    let has_broadcast = self.network.host_count() != self.network.size();
+    self.network.contains(address) && !(has_broadcast && (address == self.network.network() || address == self.network.broadcast()))
+  }
+
+  pub fn lease(&self, client_id: &str) -> Option<&Lease> {
+    self.leases.get(client_id)
+  }
+
+  // every lease, expired ones that haven't been reclaimed yet included
+  pub fn leases(&self) -> impl Iterator<Item = &Lease> {
+    self.leases.values()
+  }
+
+  /*
+    A new lease for the client, or its current one renewed. A client with a reservation always gets
+    that address. Otherwise it keeps the address it had (even if the lease ran out, as long as nobody
+    else has taken it since, and it hasn't been excluded since) or gets the first free one.
+  */
+  pub fn request(&mut self, client_id: &str) -> Result<Lease, LeaseError> {
+    check_client_id(client_id)?;
+    let now = self.clock.now();
+    let address = match self.reservations.get(client_id) {
+      Some(&reserved) => reserved,
+      None => match self.leases.get(client_id) {
+        Some(lease) if self.check_address(lease.address).is_ok() => lease.address,
+        _ => self.free_address(now)?,
+      },
+    };
+
+    // the reservation might be new and the client still holding some other address,
+    // and free_address can pick an address someone else's expired lease is still sitting on
+    let holder = self.holders.get(&address).cloned();
+    let removed: Vec<&str> = [Some(client_id), holder.as_deref()].into_iter().flatten().collect();
+    let lease = Lease { client_id: client_id.to_string(), address, expires: now.saturating_add(self.lease_time) };
+    self.save(&removed, Some(&lease))?;
+    for client_id in removed {
+      self.remove(client_id);
+    }
+    self.leases.insert(client_id.to_string(), lease.clone());
+    self.holders.insert(address, client_id.to_string());
+    Ok(lease)
+  }
+
+  /*
+    The first host that isn't excluded, reserved or held by a lease that's still running. Trying every
+    host in turn would never get to the end of a V6 pool, so it jumps over each excluded network in
+    one go. Every other address it stops at is reserved or leased, so it looks at no more addresses
+    than there are exclusions, reservations and leases (plus the free one) before it's done.
+  */
+  fn free_address(&self, now: u64) -> Result<IpAddrKind, LeaseError> {
+    let reserved: Vec<IpAddrKind> = self.reservations.values().copied().collect();
+    let (mut bits, width) = to_bits(self.network.network());
+    let (mut last, _) = to_bits(self.network.broadcast());
+    if self.network.host_count() != self.network.size() {
+      bits += 1;
+      last -= 1;
+    }
+    loop {
+      let address = from_bits(bits, width);
+      let taken = reserved.contains(&address) || self.holders.get(&address).is_some_and(|holder| !self.leases[holder].is_expired(now));
+      let next = match self.excluded.iter().find(|excluded| excluded.contains(address)) {
+        Some(excluded) => to_bits(excluded.broadcast()).0.checked_add(1),
+        None if taken => bits.checked_add(1),
+        None => return Ok(address),
+      };
+      match next {
+        Some(next) if next <= last => bits = next,
+        _ => return Err(LeaseError::Exhausted),
+      }
+    }
+  }
+
+  // the client is done with its address, it's free for anyone straight away
+  pub fn release(&mut self, client_id: &str) -> Result<Option<Lease>, LeaseError> {
+    if !self.leases.contains_key(client_id) {
+      return Ok(None);
+    }
+    self.save(&[client_id], None)?;
+    Ok(self.remove(client_id))
+  }
+
+  // drops every lease that has run out and gives them back
+  pub fn reclaim_expired(&mut self) -> Result<Vec<Lease>, LeaseError> {
+    let now = self.clock.now();
+    let mut expired: Vec<String> = self.leases.values().filter(|lease| lease.is_expired(now)).map(|lease| lease.client_id.clone()).collect();
+    if expired.is_empty() {
+      return Ok(Vec::new());
+    }
+    expired.sort();
+    let removed: Vec<&str> = expired.iter().map(String::as_str).collect();
+    self.save(&removed, None)?;
+    Ok(removed.into_iter().filter_map(|client_id| self.remove(client_id)).collect())
+  }
+
+  fn remove(&mut self, client_id: &str) -> Option<Lease> {
+    let lease = self.leases.remove(client_id)?;
+    self.holders.remove(&lease.address);
+    Some(lease)
+  }
+
+  fn load(&mut self, text: &str) -> Result<(), LeaseError> {
+    for (index, line) in text.lines().enumerate() {
+      let bad_line = |message: String| LeaseError::BadStoreLine { line: index + 1, message };
+      if line.trim().is_empty() {
+        continue;
+      }
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      let [client_id, address, expires] = fields[..] else {
+        return Err(bad_line(format!("expected `client address expires`, found {line:?}")));
+      };
+      let address: IpAddrKind = address.parse().map_err(|error| bad_line(format!("{error}")))?;
+      let expires: u64 = expires.parse().map_err(|error| bad_line(format!("expiry {expires:?}: {error}")))?;
+      // the pool's network could have changed since the file was written
+      self.check_address(address).map_err(|error| bad_line(format!("{error}")))?;
+      if self.holders.contains_key(&address) || self.leases.contains_key(client_id) {
+        return Err(bad_line(format!("{client_id} or {address} is in the file twice")));
+      }
+      // reserve could have been called before with_store
+      if let Some((other, _)) = self.reservations.iter().find(|&(other, &reserved)| reserved == address && other != client_id) {
+        return Err(bad_line(format!("{address} is reserved for {other}")));
+      }
+      self.leases.insert(client_id.to_string(), Lease { client_id: client_id.to_string(), address, expires });
+      self.holders.insert(address, client_id.to_string());
+    }
+    Ok(())
+  }
+
+  /*
+    Writes the leases as they'll be once the removed clients' leases are gone and added is in, before
+    the pool itself is changed, so a save that fails leaves the pool as it was.
+  */
+  fn save(&self, removed: &[&str], added: Option<&Lease>) -> Result<(), LeaseError> {
+    let Some(path) = &self.store else {
+      return Ok(());
+    };
+    let kept = self.leases.values().filter(|lease| !removed.contains(&lease.client_id.as_str()));
+    let mut leases: Vec<&Lease> = kept.chain(added).collect();
+    leases.sort_by(|a, b| a.client_id.cmp(&b.client_id));
+    let text: String = leases.iter().map(|lease| format!("{} {} {}\n", lease.client_id, lease.address, lease.expires)).collect();
+
+    let mut temporary = path.clone().into_os_string();
+    temporary.push(".tmp");
+    fs::write(&temporary, text)?;
+    fs::rename(&temporary, path)?;
+    Ok(())
+  }
+}
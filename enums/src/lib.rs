@@ -0,0 +1,133 @@
+/*
+  The library half of the enums package. Cargo sees src/lib.rs next to src/main.rs and builds two
+  crates with the same name: this library and the binary that uses it, so the enums the chapter
+  walks through live here where other code (and tests) can use them too.
+*/
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use color::Rgb;
+
+pub mod actor;
+pub mod canvas;
+pub mod classify;
+pub mod color;
+pub mod command;
+pub mod history;
+pub mod ipv6;
+pub mod lease;
+pub mod network;
+pub mod routing;
+pub mod wire;
+
+/*
+  Enums give us a way of saying a value can be one of a  possible set of values.
+  Like saying Rectangle, circle and triangle are all possible shapes. An enum is perfect for this
+  Example is the current two IP addresses that we can come across. 4 and 6.
+  We can enumerate all possible variants! (which is where enums get their names)
+*/
+// this is a custom data type that can be used else where in our code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpAddrKind {
+  // old way of doing this
+  // V4,
+  // V6,
+  // this now says that each variant will have associated String values
+  // V4(String),
+  // V6(String),
+  // this allows for data to be attached directly so there is no need for an extra struct
+  // each definition now has a constructor as a result of defining the enum this way
+  /*
+    another advantage over structs is being able to have each variant can have different types
+    and amounts of associated data, V4 will always have four numeric components with values between 0 and 255
+    V6 started out as a String, but a String takes anything ("hello" isn't an address), so now it's
+    the eight 16 bit segments an IPv6 address is made of; ipv6.rs turns them into text and back
+  */
+  V4(u8, u8, u8, u8),
+  V6([u16; 8]),
+}
+
+/*
+  Here an another example of an enum whoes variants each store different amount and types of data
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+  Quit, // no data associated with this variant
+  Move { x: i32, y: i32 }, // has named fields like struct does
+  Write(String), // includes a single string
+  // was three i32 values, which let a channel be -5 or 300; an Rgb from color.rs can only hold 0 to 255
+  ChangeColor(Rgb),
+}
+
+/*
+  Text is "a.b.c.d" for V4 and the RFC 5952 form from ipv6.rs for V6, so parse(to_string()) always
+  gives back the same address. Anything with a `:` in it is read as V6.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddrParseError {
+  V4 { position: usize },
+  V6(ipv6::ParseError),
+  // IpAddrKind has nowhere to keep a zone (fe80::1%eth0), ipv6::parse hands it back separately
+  Zone { position: usize },
+}
+
+impl fmt::Display for AddrParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      AddrParseError::V4 { position } => {
+        write!(f, "invalid IPv4 address at position {position}: expected four numbers from 0 to 255 separated by dots")
+      }
+      AddrParseError::V6(error) => write!(f, "{error}"),
+      AddrParseError::Zone { position } => write!(f, "unexpected zone at position {position}, IpAddrKind can't hold one"),
+    }
+  }
+}
+
+impl std::error::Error for AddrParseError {}
+
+impl FromStr for IpAddrKind {
+  type Err = AddrParseError;
+
+  fn from_str(text: &str) -> Result<Self, AddrParseError> {
+    if !text.contains(':') {
+      let [a, b, c, d] = ipv6::parse_ipv4(text, 0).map_err(|error| AddrParseError::V4 { position: error.position })?;
+      return Ok(IpAddrKind::V4(a, b, c, d));
+    }
+    match ipv6::parse(text).map_err(AddrParseError::V6)? {
+      (segments, None) => Ok(IpAddrKind::V6(segments)),
+      (_, Some(_)) => Err(AddrParseError::Zone { position: text.find('%').unwrap() }),
+    }
+  }
+}
+
+impl fmt::Display for IpAddrKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      IpAddrKind::V4(a, b, c, d) => write!(f, "{a}.{b}.{c}.{d}"),
+      IpAddrKind::V6(segments) => write!(f, "{}", ipv6::format(segments)),
+    }
+  }
+}
+
+// the standard library's version of this enum (see the comment in main.rs), same data in a different shape
+impl From<IpAddr> for IpAddrKind {
+  fn from(address: IpAddr) -> Self {
+    match address {
+      IpAddr::V4(address) => {
+        let [a, b, c, d] = address.octets();
+        IpAddrKind::V4(a, b, c, d)
+      }
+      IpAddr::V6(address) => IpAddrKind::V6(address.segments()),
+    }
+  }
+}
+
+impl From<IpAddrKind> for IpAddr {
+  fn from(address: IpAddrKind) -> Self {
+    match address {
+      IpAddrKind::V4(a, b, c, d) => IpAddr::V4(Ipv4Addr::new(a, b, c, d)),
+      IpAddrKind::V6(segments) => IpAddr::V6(Ipv6Addr::from(segments)),
+    }
+  }
+}
@@ -5,37 +5,13 @@
   Then using pattern matching in the match expression makesit easy to run different code
   for different values of an enum.  if let is also a handy convenient to handle enums in code!
 */
-/*
-  Enums give us a way of saying a value can be one of a  possible set of values.
-  Like saying Rectangle, circle and triangle are all possible shapes. An enum is perfect for this
-  Example is the current two IP addresses that we can come across. 4 and 6.
-  We can enumerate all possible variants! (which is where enums get their names)
-*/
-// this is a custom data type that can be used else where in our code.
-enum IpAddrKind {
-  // old way of doing this
-  // V4,
-  // V6,
-  // this now says that each variant will have associated String values
-  // V4(String),
-  // V6(String),
-  // this allows for data to be attached directly so there is no need for an extra struct
-  // each definition now has a constructor as a result of defining the enum this way
-  /*
-    another advantage over structs is being able to have each variant can have different types
-    and amounts of associated data, V4 will always have four numeric components with values between 0 and 255
-    We can store V4 like this but keep V6 as a String; this would be impossible with a struct
-  */
-  V4(u8, u8, u8, u8),
-  V6(String),
-}
+// the walkthrough makes values just to show how they're written, most of them are never used
+#![allow(dead_code, unused_variables)]
 
-// second iteration where both types take in a string
-let home = IpAddr::V4(String::from("127.0.0.1"));
-let loopback = IpAddr::V6(String::from("::1"));
-// third iteration
-let home = IpAddr::V4(127, 0, 0, 1);
-let loopback = IpAddr::V6(String::from("::1"));
+// IpAddrKind moved to src/lib.rs so the rest of the package can use it
+use enums::canvas::Canvas;
+use enums::color::Rgb;
+use enums::{ipv6, IpAddrKind, Message};
 
 /*
   this is a common use case that the stadard library as this implemented
@@ -54,16 +30,7 @@ let loopback = IpAddr::V6(String::from("::1"));
 // enums can contain any type of data, including another enum
 // and if we implement our own it wont conflict because it isn't brought into scope from the standard library
 
-/*
-  Here an another example of an enum whoes variants each store different amount and types of data
-*/
-
-enum Message {
-  Quit, // no data associated with this variant
-  Move { x: i32, y: i32 }, // has named fields like struct does
-  Write(String), // includes a single string
-  ChangeColor(i32, i32, i32), // includes three i32 values
-}
+// Message, the next example, lives in src/lib.rs too
 // this is kinda similar to defining different struct definitions except:
 // it doesnt use the `struct` keyword
 // the variants are grouped together under the `Message` enum type
@@ -74,17 +41,10 @@ struct MoveMessage {
     y: i32,
 }
 struct WriteMessage(String); // tuple struct
-struct ChangeColorMessage(i32, i32, i32); // tuple struct
+struct ChangeColorMessage(Rgb); // tuple struct
 // but being different types we couldnt define a function that takes and correctly outputs for each of these messages
 // and similar to adding methods on structs we can add methods on enums
-impl Message {
-  fn call(&self) {
-      // method body would be defined here
-      // using self to get the value that we called the method on.
-  }
-}
-let m = Message::Write(String::from("hello"));
-m.call();
+// (Message::call is in src/canvas.rs, it draws on a Canvas)
 // another useful enum is called Option
 /*
   The advantages of using the Option enum over null values
@@ -101,15 +61,12 @@ m.call();
 /*
   it represents the idea a value could be something or nothing
 */
-enum Option<T> {
-    None,
-    Some(T),
-}
-// how it is used
-let some_number = Some(5); // these first two do not need a type as it can be inferred
-let some_char = Some('e');
-let absent_number: Option<i32> = None; // but this does need a type as it cannot be inferred
-// so we need to specify the type of the None value
+// this is how the standard library defines it (defining it again here would hide the real one)
+// enum Option<T> {
+//     None,
+//     Some(T),
+// }
+// how it is used is in main below
 
 /*
   using the option means we can use values that may or may not be there and the compiler 
@@ -152,10 +109,40 @@ fn main() {
   let four = IpAddrKind::V4;
   let six = IpAddrKind::V6;
 
-  
-  
+  // second iteration where both types take in a string
+  // let home = IpAddrKind::V4(String::from("127.0.0.1"));
+  // let loopback = IpAddrKind::V6(String::from("::1"));
+  // third iteration
+  let home = IpAddrKind::V4(127, 0, 0, 1);
+  let loopback = IpAddrKind::V6([0, 0, 0, 0, 0, 0, 0, 1]);
+  // the text form goes through ipv6::parse, which says where it went wrong if it isn't an address
+  let documentation = ipv6::parse("2001:DB8:0:0:0:0:0:1").unwrap().0;
+  println!("{}", ipv6::format(&documentation)); // 2001:db8::1
+  println!("{}", ipv6::parse("2001:db8::g").unwrap_err()); // invalid IPv6 address at position 10: unexpected character 'g'
+  // or for either kind, FromStr and Display, and .into() to swap over to the standard library's IpAddr
+  let parsed: IpAddrKind = "127.0.0.1".parse().unwrap();
+  let standard: std::net::IpAddr = loopback.into();
+  println!("{parsed} {loopback} {}", standard.is_loopback()); // 127.0.0.1 ::1 true
+  // and what kind of address each one is
+  println!("{:?} {:?}", home.classify(), loopback.classify()); // Loopback Loopback
+  println!("{:?}", "ff02::1".parse::<IpAddrKind>().unwrap().classify()); // Multicast(LinkLocal)
+
+  let mut canvas = Canvas::new(12, 3).unwrap();
+  let m = Message::Write(String::from("hello"));
+  Message::Move { x: 1, y: 1 }.call(&mut canvas);
+  m.call(&mut canvas);
+  print!("{}", canvas.to_text());
+  // ChangeColor carries an Rgb, which can't be out of range in the first place
+  let orange: Rgb = "#f80".parse().unwrap();
+  Message::ChangeColor(orange).call(&mut canvas);
+  println!("{} {:.1}", canvas.pen(), orange.contrast_ratio(Rgb::WHITE)); // #ff8800 2.4
+
+  // how Option is used
+  let some_number = Some(5); // these first two do not need a type as it can be inferred
+  let some_char = Some('e');
+  let absent_number: Option<i32> = None; // but this does need a type as it cannot be inferred
+  // so we need to specify the type of the None value
 }
 
-// having an enum like this lets each IP type be the same, aka IpAddrKind and in a function it can take:
-// fn route(ip_kind: IpAddrKind) { ... }
-// and then we can pass in either IpAddrKind::V4 or IpAddrKind::V6 when we call the function!
+// having an enum like this lets each IP type be the same, aka IpAddrKind, so a function can take either
+// IpAddrKind::V4 or IpAddrKind::V6 (RoutingTable::lookup in src/routing.rs does)
@@ -0,0 +1,266 @@
+/*
+  A network is an address plus a prefix length, written 10.0.0.0/8 or 2001:db8::/32: the first
+  `prefix` bits are the network part and every address that shares them is inside the network.
+  The maths is all done on the address as one number, a u32 fits V4 but V6 needs all 128 bits so
+  everything is a u128 and `width` (32 or 128) says how many of those bits are in use.
+
+  The address is kept the way it was written, so 10.1.2.3/8 (an interface address together with
+  the size of its network) stays 10.1.2.3/8. network() gives the 10.0.0.0 with the host bits cleared.
+*/
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{AddrParseError, IpAddrKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkError {
+  Address(AddrParseError),
+  MissingPrefix,
+  // the prefix isn't a number, position is where it starts
+  InvalidPrefix { position: usize },
+  PrefixTooLong { prefix: u8, max: u8 },
+  // splitting into subnets with a shorter prefix than the network has
+  PrefixTooShort { prefix: u8, min: u8 },
+}
+
+impl fmt::Display for NetworkError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      NetworkError::Address(error) => write!(f, "{error}"),
+      NetworkError::MissingPrefix => write!(f, "expected a /prefix after the address"),
+      NetworkError::InvalidPrefix { position } => write!(f, "invalid prefix length at position {position}"),
+      NetworkError::PrefixTooLong { prefix, max } => write!(f, "prefix /{prefix} is longer than the address ({max} bits)"),
+      NetworkError::PrefixTooShort { prefix, min } => write!(f, "subnets can't have a shorter prefix (/{prefix}) than the network (/{min})"),
+    }
+  }
+}
+
+impl std::error::Error for NetworkError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IpNetwork {
+  address: IpAddrKind,
+  prefix: u8,
+}
+
+// the address as a number and how many bits it has
+pub(crate) fn to_bits(address: IpAddrKind) -> (u128, u8) {
+  match address {
+    IpAddrKind::V4(a, b, c, d) => (u128::from(u32::from_be_bytes([a, b, c, d])), 32),
+    IpAddrKind::V6(segments) => (segments.iter().fold(0, |bits, &segment| bits << 16 | u128::from(segment)), 128),
+  }
+}
+
+pub(crate) fn from_bits(bits: u128, width: u8) -> IpAddrKind {
+  if width == 32 {
+    let [a, b, c, d] = (bits as u32).to_be_bytes();
+    return IpAddrKind::V4(a, b, c, d);
+  }
+  let mut segments = [0; 8];
+  for (i, segment) in segments.iter_mut().enumerate() {
+    *segment = (bits >> (112 - 16 * i)) as u16;
+  }
+  IpAddrKind::V6(segments)
+}
+
+// the first `prefix` bits of a `width` bit number set
+pub(crate) fn mask(prefix: u8, width: u8) -> u128 {
+  if prefix == 0 {
+    return 0;
+  }
+  let all = if width == 128 { u128::MAX } else { (1 << width) - 1 };
+  all & !(u128::MAX.checked_shr(u32::from(prefix)).unwrap_or(0) >> (128 - width))
+}
+
+impl IpNetwork {
+  pub fn new(address: IpAddrKind, prefix: u8) -> Result<Self, NetworkError> {
+    let (_, width) = to_bits(address);
+    if prefix > width {
+      return Err(NetworkError::PrefixTooLong { prefix, max: width });
+    }
+    Ok(IpNetwork { address, prefix })
+  }
+
+  pub fn address(&self) -> IpAddrKind {
+    self.address
+  }
+
+  pub fn prefix(&self) -> u8 {
+    self.prefix
+  }
+
+  fn width(&self) -> u8 {
+    to_bits(self.address).1
+  }
+
+  fn first(&self) -> u128 {
+    to_bits(self.address).0 & mask(self.prefix, self.width())
+  }
+
+  fn last(&self) -> u128 {
+    self.first() | (mask(self.width(), self.width()) & !mask(self.prefix, self.width()))
+  }
+
+  pub fn netmask(&self) -> IpAddrKind {
+    from_bits(mask(self.prefix, self.width()), self.width())
+  }
+
+  pub fn network(&self) -> IpAddrKind {
+    from_bits(self.first(), self.width())
+  }
+
+  // the last address in the network, V6 has no broadcast as such but it's the same address
+  pub fn broadcast(&self) -> IpAddrKind {
+    from_bits(self.last(), self.width())
+  }
+
+  // the same network with the host bits cleared, 10.1.2.3/8 becomes 10.0.0.0/8
+  pub fn trunc(&self) -> IpNetwork {
+    IpNetwork { address: self.network(), prefix: self.prefix }
+  }
+
+  // every address from network() to broadcast(), ::/0 has 2^128 which is one too many for a u128 so it stops at u128::MAX
+  pub fn size(&self) -> u128 {
+    let host_bits = self.width() - self.prefix;
+    if host_bits == 128 {
+      u128::MAX
+    } else {
+      1 << host_bits
+    }
+  }
+
+  /*
+    the addresses a host can have: for V4 the network and broadcast addresses are taken, except in a
+    /31 (a point to point link, RFC 3021) and a /32 where there's no room for them. V6 uses them all.
+  */
+  pub fn host_count(&self) -> u128 {
+    match (self.width(), self.prefix) {
+      (32, 0..=30) => self.size() - 2,
+      _ => self.size(),
+    }
+  }
+
+  pub fn contains(&self, address: IpAddrKind) -> bool {
+    let (bits, width) = to_bits(address);
+    width == self.width() && bits & mask(self.prefix, width) == self.first()
+  }
+
+  // other is the same network or a smaller one inside this one
+  pub fn contains_network(&self, other: &IpNetwork) -> bool {
+    other.prefix >= self.prefix && self.contains(other.address)
+  }
+
+  pub fn hosts(&self) -> Hosts {
+    let (mut first, mut last) = (self.first(), self.last());
+    if self.width() == 32 && self.prefix <= 30 {
+      first += 1;
+      last -= 1;
+    }
+    Hosts { next: Some(first), last, width: self.width() }
+  }
+
+  // splits the network into the networks with the longer prefix that make it up, 10.0.0.0/8 into /9s gives 10.0.0.0/9 and 10.128.0.0/9
+  pub fn subnets(&self, prefix: u8) -> Result<Subnets, NetworkError> {
+    if prefix > self.width() {
+      return Err(NetworkError::PrefixTooLong { prefix, max: self.width() });
+    }
+    if prefix < self.prefix {
+      return Err(NetworkError::PrefixTooShort { prefix, min: self.prefix });
+    }
+    let last = self.last() & mask(prefix, self.width());
+    Ok(Subnets { next: Some(self.first()), last, prefix, width: self.width() })
+  }
+
+  // the network one bit shorter that this one is half of, None for a /0
+  pub fn supernet(&self) -> Option<IpNetwork> {
+    let prefix = self.prefix.checked_sub(1)?;
+    Some(IpNetwork { address: from_bits(self.first() & mask(prefix, self.width()), self.width()), prefix })
+  }
+
+  /*
+    The smallest list of networks covering exactly the same addresses as the ones given: networks
+    inside another one are dropped and two halves of the same supernet are joined into it (over and
+    over, so four /26s next to each other become one /24). V4 comes before V6, then by address.
+  */
+  pub fn aggregate(networks: &[IpNetwork]) -> Vec<IpNetwork> {
+    let mut sorted: Vec<IpNetwork> = networks.iter().map(IpNetwork::trunc).collect();
+    sorted.sort_by_key(|network| (network.width(), network.first(), network.prefix));
+
+    let mut merged: Vec<IpNetwork> = Vec::new();
+    for network in sorted {
+      if merged.last().is_some_and(|last| last.contains_network(&network)) {
+        continue;
+      }
+      let mut network = network;
+      // keep joining with the one before while the two are the halves of a supernet
+      while let Some(last) = merged.last() {
+        match network.supernet() {
+          Some(supernet) if last.prefix == network.prefix && last.supernet() == Some(supernet) => {
+            merged.pop();
+            network = supernet;
+          }
+          _ => break,
+        }
+      }
+      merged.push(network);
+    }
+    merged
+  }
+}
+
+impl FromStr for IpNetwork {
+  type Err = NetworkError;
+
+  fn from_str(text: &str) -> Result<Self, NetworkError> {
+    let (address, prefix) = text.split_once('/').ok_or(NetworkError::MissingPrefix)?;
+    let address: IpAddrKind = address.parse().map_err(NetworkError::Address)?;
+    let position = text.len() - prefix.len();
+    // only plain digits, u8's FromStr would take a + sign too, and no leading zeros (/08) the same as the V4 parts
+    let leading_zero = prefix.len() > 1 && prefix.starts_with('0');
+    if prefix.is_empty() || prefix.len() > 3 || leading_zero || !prefix.bytes().all(|byte| byte.is_ascii_digit()) {
+      return Err(NetworkError::InvalidPrefix { position });
+    }
+    IpNetwork::new(address, prefix.parse().map_err(|_| NetworkError::InvalidPrefix { position })?)
+  }
+}
+
+impl fmt::Display for IpNetwork {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}/{}", self.address, self.prefix)
+  }
+}
+
+pub struct Hosts {
+  // None once the last one has been handed out (the last can be u128::MAX so there's no last + 1 to stop at)
+  next: Option<u128>,
+  last: u128,
+  width: u8,
+}
+
+impl Iterator for Hosts {
+  type Item = IpAddrKind;
+
+  fn next(&mut self) -> Option<IpAddrKind> {
+    let bits = self.next?;
+    self.next = if bits == self.last { None } else { Some(bits + 1) };
+    Some(from_bits(bits, self.width))
+  }
+}
+
+pub struct Subnets {
+  next: Option<u128>,
+  last: u128,
+  prefix: u8,
+  width: u8,
+}
+
+impl Iterator for Subnets {
+  type Item = IpNetwork;
+
+  fn next(&mut self) -> Option<IpNetwork> {
+    let bits = self.next?;
+    // the step is the size of one subnet, 1 << (width - prefix)
+    self.next = if bits == self.last { None } else { Some(bits + (1 << (self.width - self.prefix))) };
+    Some(IpNetwork { address: from_bits(bits, self.width), prefix: self.prefix })
+  }
+}
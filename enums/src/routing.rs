@@ -0,0 +1,207 @@
+/*
+  A routing table: routes are networks (10.0.0.0/8, 2001:db8::/32) that point at something, like
+  the next hop to send a packet to. Looking up an address finds the longest prefix match, the most
+  specific route that contains it, so 10.1.2.3 goes via 10.1.0.0/16 rather than 10.0.0.0/8 when
+  both are there, and a /0 default route catches whatever nothing else does.
+
+  Routes live in a binary trie, one for V4 and one for V6: from the root, every bit of the network
+  address picks the left (0) or right (1) child, and a /n route is stored n levels down. A lookup
+  follows the address's bits down as far as the trie goes and keeps the last route it passed, so it
+  takes at most 32 (or 128) steps no matter how many routes there are.
+
+  The file format for load is one route per line, the network then what it points at:
+    # comment
+    0.0.0.0/0      192.168.1.1
+    10.0.0.0/8     10.255.255.254
+    2001:db8::/32  fe80::1
+*/
+use std::path::Path;
+use std::{fmt, fs, io};
+
+use crate::network::{from_bits, mask, to_bits, IpNetwork};
+use crate::IpAddrKind;
+
+struct Node<T> {
+  // indexes into RoutingTable::nodes, 0 bit then 1 bit
+  children: [Option<usize>; 2],
+  route: Option<T>,
+}
+
+impl<T> Node<T> {
+  fn new() -> Self {
+    Node { children: [None, None], route: None }
+  }
+}
+
+pub struct RoutingTable<T> {
+  // nodes[0] is the V4 root and nodes[1] the V6 one, withdrawn routes leave their nodes behind
+  nodes: Vec<Node<T>>,
+  len: usize,
+}
+
+// the bit `depth` places from the top of a `width` bit address
+fn bit(bits: u128, width: u8, depth: u8) -> usize {
+  (bits >> (width - 1 - depth) & 1) as usize
+}
+
+fn root(width: u8) -> usize {
+  if width == 32 {
+    0
+  } else {
+    1
+  }
+}
+
+impl<T> Default for RoutingTable<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> RoutingTable<T> {
+  pub fn new() -> Self {
+    RoutingTable { nodes: vec![Node::new(), Node::new()], len: 0 }
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  // the node a network's route lives in, if the trie goes that deep
+  fn find(&self, network: &IpNetwork) -> Option<usize> {
+    let (bits, width) = to_bits(network.address());
+    let mut node = root(width);
+    for depth in 0..network.prefix() {
+      node = self.nodes[node].children[bit(bits, width, depth)]?;
+    }
+    Some(node)
+  }
+
+  // the host bits of the network are ignored, 10.1.2.3/8 is the same route as 10.0.0.0/8; gives back the route it replaced
+  pub fn insert(&mut self, network: IpNetwork, route: T) -> Option<T> {
+    let (bits, width) = to_bits(network.address());
+    let mut node = root(width);
+    for depth in 0..network.prefix() {
+      let side = bit(bits, width, depth);
+      node = match self.nodes[node].children[side] {
+        Some(child) => child,
+        None => {
+          self.nodes.push(Node::new());
+          let child = self.nodes.len() - 1;
+          self.nodes[node].children[side] = Some(child);
+          child
+        }
+      };
+    }
+    let replaced = self.nodes[node].route.replace(route);
+    if replaced.is_none() {
+      self.len += 1;
+    }
+    replaced
+  }
+
+  // withdraws the route for exactly this network, a longer or shorter one for the same addresses stays
+  pub fn remove(&mut self, network: &IpNetwork) -> Option<T> {
+    let node = self.find(network)?;
+    let removed = self.nodes[node].route.take()?;
+    self.len -= 1;
+    Some(removed)
+  }
+
+  // the route for exactly this network, lookup is the one that finds the best match for an address
+  pub fn get(&self, network: &IpNetwork) -> Option<&T> {
+    self.nodes[self.find(network)?].route.as_ref()
+  }
+
+  // longest prefix match: the most specific network containing the address and its route
+  pub fn lookup(&self, address: IpAddrKind) -> Option<(IpNetwork, &T)> {
+    let (bits, width) = to_bits(address);
+    let mut node = root(width);
+    let mut best = self.nodes[node].route.as_ref().map(|route| (0, route));
+    for depth in 0..width {
+      match self.nodes[node].children[bit(bits, width, depth)] {
+        Some(child) => node = child,
+        None => break,
+      }
+      if let Some(route) = &self.nodes[node].route {
+        best = Some((depth + 1, route));
+      }
+    }
+    let (prefix, route) = best?;
+    Some((IpNetwork::new(from_bits(bits & mask(prefix, width), width), prefix).unwrap(), route))
+  }
+
+  // every route, V4 first, each family in address order with a shorter prefix before a longer one at the same address
+  pub fn routes(&self) -> Vec<(IpNetwork, &T)> {
+    let mut routes = Vec::with_capacity(self.len);
+    for width in [32, 128] {
+      // (node, address bits so far, depth), right pushed before left so left comes out first
+      let mut stack = vec![(root(width), 0u128, 0u8)];
+      while let Some((node, bits, depth)) = stack.pop() {
+        if let Some(route) = &self.nodes[node].route {
+          routes.push((IpNetwork::new(from_bits(bits, width), depth).unwrap(), route));
+        }
+        for side in [1, 0] {
+          if let Some(child) = self.nodes[node].children[side] {
+            stack.push((child, bits | (side as u128) << (width - 1 - depth), depth + 1));
+          }
+        }
+      }
+    }
+    routes
+  }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+  Io(io::Error),
+  // line numbers start at 1
+  Line { line: usize, message: String },
+}
+
+impl fmt::Display for LoadError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      LoadError::Io(error) => write!(f, "{error}"),
+      LoadError::Line { line, message } => write!(f, "line {line}: {message}"),
+    }
+  }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+  fn from(error: io::Error) -> Self {
+    LoadError::Io(error)
+  }
+}
+
+impl RoutingTable<IpAddrKind> {
+  // a table of next hops from the text of a routing file (see the top of this file), a network listed twice keeps the last one
+  pub fn parse(text: &str) -> Result<Self, LoadError> {
+    let mut table = RoutingTable::new();
+    for (index, line) in text.lines().enumerate() {
+      let line_error = |message: String| LoadError::Line { line: index + 1, message };
+      let line = line.split('#').next().unwrap().trim();
+      if line.is_empty() {
+        continue;
+      }
+      let mut fields = line.split_whitespace();
+      let (Some(network), Some(next_hop), None) = (fields.next(), fields.next(), fields.next()) else {
+        return Err(line_error(format!("expected a network and a next hop, found {line:?}")));
+      };
+      let network: IpNetwork = network.parse().map_err(|error| line_error(format!("{error}")))?;
+      let next_hop: IpAddrKind = next_hop.parse().map_err(|error| line_error(format!("{error}")))?;
+      table.insert(network, next_hop);
+    }
+    Ok(table)
+  }
+
+  pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+    Self::parse(&fs::read_to_string(path)?)
+  }
+}
@@ -0,0 +1,293 @@
+/*
+  A compact binary form of Message for sending between processes. Each message is one frame:
+    length   varint, how many bytes of payload follow
+    tag      one byte: 0 Quit, 1 Move, 2 Write, 3 ChangeColor
+    fields   Move: x, y as zigzag varint i32s
+             Write: a varint byte length, then that many bytes of UTF-8
+             ChangeColor: r, g, b as one byte each
+  A varint is 7 bits per byte, lowest bits first, with the top bit set on every byte but the last,
+  so small numbers take one byte. Zigzag maps 0, -1, 1, -2, ... to 0, 1, 2, 3, ... first so small
+  negative numbers stay small too. move 3 4 is 4 bytes: [3, 1, 6, 8].
+
+  The length at the front means a reader always knows where the next message starts, even when it
+  can't make sense of this one. Decoder takes bytes in whatever pieces they arrive in and hands
+  out each message once its whole frame is there; MessageReader does that over any Read and
+  write_message puts a frame on any Write.
+*/
+use std::io::{self, Read, Write};
+use std::{fmt, str};
+
+use crate::color::Rgb;
+use crate::Message;
+
+const QUIT: u8 = 0;
+const MOVE: u8 = 1;
+const WRITE: u8 = 2;
+const CHANGE_COLOR: u8 = 3;
+
+// frames claiming to be bigger than this are rejected instead of waiting for (and buffering) that much,
+// and encode won't make one (a Write of a huge String) since the other end would never take it
+pub const MAX_FRAME: usize = 1 << 20;
+
+#[derive(Debug)]
+pub enum WireError {
+  // the input ended partway through a frame, or a frame ended partway through its fields
+  Truncated,
+  UnknownTag(u8),
+  InvalidUtf8(str::Utf8Error),
+  // a varint longer than its type can hold
+  VarintOverflow,
+  FrameTooLarge(usize),
+  // the fields ended before the frame did
+  TrailingBytes(usize),
+  Io(io::Error),
+}
+
+impl fmt::Display for WireError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      WireError::Truncated => write!(f, "truncated frame"),
+      WireError::UnknownTag(tag) => write!(f, "unknown message tag {tag}"),
+      WireError::InvalidUtf8(error) => write!(f, "write text is not UTF-8: {error}"),
+      WireError::VarintOverflow => write!(f, "varint is too long"),
+      WireError::FrameTooLarge(length) => write!(f, "frame of {length} bytes is over the {MAX_FRAME} byte limit"),
+      WireError::TrailingBytes(count) => write!(f, "{count} bytes left over at the end of the frame"),
+      WireError::Io(error) => write!(f, "{error}"),
+    }
+  }
+}
+
+impl std::error::Error for WireError {}
+
+impl From<io::Error> for WireError {
+  fn from(error: io::Error) -> Self {
+    WireError::Io(error)
+  }
+}
+
+fn put_varint(bytes: &mut Vec<u8>, mut value: u32) {
+  while value >= 0x80 {
+    bytes.push(value as u8 | 0x80);
+    value >>= 7;
+  }
+  bytes.push(value as u8);
+}
+
+fn put_i32(bytes: &mut Vec<u8>, value: i32) {
+  put_varint(bytes, ((value << 1) ^ (value >> 31)) as u32);
+}
+
+// a length that can go in a frame, anything over MAX_FRAME the other end would refuse
+fn frame_length(length: usize) -> Result<u32, WireError> {
+  match u32::try_from(length) {
+    Ok(fits) if length <= MAX_FRAME => Ok(fits),
+    _ => Err(WireError::FrameTooLarge(length)),
+  }
+}
+
+// the value and how many bytes it took, None if bytes ends before the varint does
+fn get_varint(bytes: &[u8]) -> Result<Option<(u32, usize)>, WireError> {
+  let mut value: u32 = 0;
+  for (i, &byte) in bytes.iter().enumerate() {
+    // 5 bytes hold 35 bits, only the low 4 of the last byte fit in a u32
+    if i == 4 && byte > 0x0f {
+      return Err(WireError::VarintOverflow);
+    }
+    value |= u32::from(byte & 0x7f) << (7 * i);
+    if byte & 0x80 == 0 {
+      return Ok(Some((value, i + 1)));
+    }
+  }
+  Ok(None)
+}
+
+// reads the fields of a payload from the front
+struct Fields<'a>(&'a [u8]);
+
+impl Fields<'_> {
+  fn byte(&mut self) -> Result<u8, WireError> {
+    let (&byte, rest) = self.0.split_first().ok_or(WireError::Truncated)?;
+    self.0 = rest;
+    Ok(byte)
+  }
+
+  fn varint(&mut self) -> Result<u32, WireError> {
+    let (value, used) = get_varint(self.0)?.ok_or(WireError::Truncated)?;
+    self.0 = &self.0[used..];
+    Ok(value)
+  }
+
+  fn i32(&mut self) -> Result<i32, WireError> {
+    let zigzag = self.varint()?;
+    Ok((zigzag >> 1) as i32 ^ -((zigzag & 1) as i32))
+  }
+}
+
+// FrameTooLarge if the frame would be over MAX_FRAME
+pub fn encode(message: &Message) -> Result<Vec<u8>, WireError> {
+  let mut payload = Vec::new();
+  match message {
+    Message::Quit => payload.push(QUIT),
+    Message::Move { x, y } => {
+      payload.push(MOVE);
+      put_i32(&mut payload, *x);
+      put_i32(&mut payload, *y);
+    }
+    Message::Write(text) => {
+      // checked on its own first so a huge string is turned down before it's copied
+      let length = frame_length(text.len())?;
+      payload.push(WRITE);
+      put_varint(&mut payload, length);
+      payload.extend_from_slice(text.as_bytes());
+    }
+    Message::ChangeColor(Rgb(r, g, b)) => payload.extend_from_slice(&[CHANGE_COLOR, *r, *g, *b]),
+  }
+  let length = frame_length(payload.len())?;
+  let mut frame = Vec::with_capacity(payload.len() + 5);
+  put_varint(&mut frame, length);
+  frame.extend(payload);
+  Ok(frame)
+}
+
+// a whole payload (the frame without its length) into a message
+fn decode_payload(payload: &[u8]) -> Result<Message, WireError> {
+  let (&tag, rest) = payload.split_first().ok_or(WireError::Truncated)?;
+  let mut fields = Fields(rest);
+  let message = match tag {
+    QUIT => Message::Quit,
+    MOVE => Message::Move { x: fields.i32()?, y: fields.i32()? },
+    WRITE => {
+      let length = fields.varint()? as usize;
+      let text = fields.0.get(..length).ok_or(WireError::Truncated)?;
+      fields.0 = &fields.0[length..];
+      Message::Write(str::from_utf8(text).map_err(WireError::InvalidUtf8)?.to_string())
+    }
+    CHANGE_COLOR => Message::ChangeColor(Rgb(fields.byte()?, fields.byte()?, fields.byte()?)),
+    unknown => return Err(WireError::UnknownTag(unknown)),
+  };
+  match fields.0.len() {
+    0 => Ok(message),
+    left => Err(WireError::TrailingBytes(left)),
+  }
+}
+
+// where the payload of the first frame in bytes starts and ends, None if the frame isn't all there yet
+fn frame(bytes: &[u8]) -> Result<Option<(usize, usize)>, WireError> {
+  let Some((length, header)) = get_varint(bytes)? else {
+    return Ok(None);
+  };
+  let length = length as usize;
+  if length > MAX_FRAME {
+    return Err(WireError::FrameTooLarge(length));
+  }
+  Ok((bytes.len() >= header + length).then_some((header, header + length)))
+}
+
+// the first message in bytes and how many bytes its frame took, or None if the frame isn't all there yet
+pub fn decode(bytes: &[u8]) -> Result<Option<(Message, usize)>, WireError> {
+  let Some((start, end)) = frame(bytes)? else {
+    return Ok(None);
+  };
+  Ok(Some((decode_payload(&bytes[start..end])?, end)))
+}
+
+// bytes go in with feed as they arrive, messages come out of next_message as soon as their frame is complete
+#[derive(Debug, Default)]
+pub struct Decoder {
+  buffer: Vec<u8>,
+  // where the first frame not handed out yet starts, the bytes before it are only dropped on the next feed
+  // so a read that brought in a lot of frames isn't shifted down once per message
+  start: usize,
+}
+
+impl Decoder {
+  pub fn new() -> Self {
+    Decoder::default()
+  }
+
+  pub fn feed(&mut self, bytes: &[u8]) {
+    self.buffer.drain(..self.start);
+    self.start = 0;
+    self.buffer.extend_from_slice(bytes);
+  }
+
+  // bytes fed in that aren't part of a message handed out yet
+  pub fn pending(&self) -> usize {
+    self.buffer.len() - self.start
+  }
+
+  /*
+    Ok(None) means it needs more bytes. A frame that fails to decode is dropped from the buffer
+    along with the error, so the next call carries on with the frame after it; a bad length can't
+    be skipped like that so it stays an error every time.
+  */
+  pub fn next_message(&mut self) -> Result<Option<Message>, WireError> {
+    let bytes = &self.buffer[self.start..];
+    let Some((start, end)) = frame(bytes)? else {
+      return Ok(None);
+    };
+    let payload = &bytes[start..end];
+    self.start += end;
+    decode_payload(payload).map(Some)
+  }
+}
+
+// a message too big to send is InvalidInput, with the WireError inside
+pub fn write_message(writer: &mut impl Write, message: &Message) -> io::Result<()> {
+  let frame = encode(message).map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+  writer.write_all(&frame)
+}
+
+// messages from any Read, reading as much as it needs when it needs it
+pub struct MessageReader<R> {
+  reader: R,
+  decoder: Decoder,
+  // after an error there's no getting past (the input ended or broke, or a bad frame length) it only hands out None
+  finished: bool,
+}
+
+impl<R: Read> MessageReader<R> {
+  pub fn new(reader: R) -> Self {
+    MessageReader { reader, decoder: Decoder::new(), finished: false }
+  }
+
+  // Ok(None) at the end of the input, Truncated if it ended partway through a frame
+  pub fn read_message(&mut self) -> Result<Option<Message>, WireError> {
+    let mut chunk = [0; 4096];
+    while !self.finished {
+      let pending = self.decoder.pending();
+      match self.decoder.next_message() {
+        Ok(Some(message)) => return Ok(Some(message)),
+        Ok(None) => {}
+        // a frame that didn't decode is gone from the buffer and the next one can still be read
+        Err(error) => {
+          self.finished = self.decoder.pending() == pending;
+          return Err(error);
+        }
+      }
+      match self.reader.read(&mut chunk) {
+        Ok(0) => {
+          self.finished = true;
+          if pending > 0 {
+            return Err(WireError::Truncated);
+          }
+        }
+        Ok(read) => self.decoder.feed(&chunk[..read]),
+        Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+        Err(error) => {
+          self.finished = true;
+          return Err(error.into());
+        }
+      }
+    }
+    Ok(None)
+  }
+}
+
+impl<R: Read> Iterator for MessageReader<R> {
+  type Item = Result<Message, WireError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.read_message().transpose()
+  }
+}
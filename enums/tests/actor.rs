@@ -0,0 +1,74 @@
+// workers and the router: acknowledgements, stopping on Quit and the canvases they hand back
+use enums::actor::{Ack, Router, RouterError};
+use enums::canvas::{Canvas, CanvasError};
+use enums::Message;
+
+fn write(text: &str) -> Message {
+  Message::Write(String::from(text))
+}
+
+#[test]
+fn broadcast_reaches_every_worker() {
+  let router = Router::new(3, 4, 1).unwrap();
+  assert_eq!(router.broadcast(&write("ab")), 3);
+  assert_eq!(router.broadcast(&Message::Quit), 3);
+  let report = router.finish();
+  assert_eq!(report.canvases.len(), 3);
+  for canvas in &report.canvases {
+    assert_eq!(canvas.to_text(), "ab..\n");
+    assert!(canvas.is_finished());
+  }
+  assert_eq!(report.acks.len(), 6);
+  for worker in 0..3 {
+    let acks: Vec<Ack> = report.acks.iter().copied().filter(|ack| ack.worker == worker).collect();
+    assert_eq!(acks, [Ack { worker, sequence: 1, quit: false }, Ack { worker, sequence: 2, quit: true }]);
+  }
+}
+
+#[test]
+fn send_goes_to_one_worker_only() {
+  let router = Router::new(2, 3, 1).unwrap();
+  router.send(1, write("x")).unwrap();
+  assert_eq!(router.wait_for_ack(), Some(Ack { worker: 1, sequence: 1, quit: false }));
+  assert_eq!(router.send(2, write("y")), Err(RouterError::UnknownWorker(2)));
+  let report = router.finish();
+  assert_eq!(report.canvases[0], Canvas::new(3, 1).unwrap());
+  assert_eq!(report.canvases[1].to_text(), "x..\n");
+  // the one wait_for_ack took isn't in the report again
+  assert!(report.acks.is_empty());
+}
+
+#[test]
+fn a_stopped_worker_refuses_messages() {
+  let router = Router::new(2, 2, 1).unwrap();
+  router.send(0, Message::Quit).unwrap();
+  assert_eq!(router.wait_for_ack(), Some(Ack { worker: 0, sequence: 1, quit: true }));
+  // the ack comes just before the thread ends, so wait for the mailbox to close
+  while router.send(0, write("late")).is_ok() {
+    std::thread::yield_now();
+  }
+  assert_eq!(router.send(0, write("late")), Err(RouterError::WorkerStopped(0)));
+  assert_eq!(router.broadcast(&write("b")), 1);
+  let report = router.finish();
+  assert_eq!(report.canvases[0].to_text(), "..\n");
+  assert_eq!(report.canvases[1].to_text(), "b.\n");
+}
+
+#[test]
+fn finish_without_quit_applies_everything_queued() {
+  let router = Router::new(1, 5, 1).unwrap();
+  for x in 0..5 {
+    router.send(0, Message::Move { x, y: 0 }).unwrap();
+    router.send(0, write("#")).unwrap();
+  }
+  let report = router.finish();
+  assert_eq!(report.canvases[0].to_text(), "#####\n");
+  assert!(!report.canvases[0].is_finished());
+  let sequences: Vec<u64> = report.acks.iter().map(|ack| ack.sequence).collect();
+  assert_eq!(sequences, (1..=10).collect::<Vec<_>>());
+}
+
+#[test]
+fn a_canvas_too_big_starts_no_workers() {
+  assert!(matches!(Router::new(2, usize::MAX, 2), Err(CanvasError::TooBig { .. })));
+}
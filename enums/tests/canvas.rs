@@ -0,0 +1,109 @@
+// scripted message sequences run against a small canvas, checked through the text and PPM renderers
+use enums::canvas::{Canvas, CanvasError, Cell, MAX_CELLS};
+use enums::color::Rgb;
+use enums::Message;
+
+fn write(text: &str) -> Message {
+  Message::Write(String::from(text))
+}
+
+#[test]
+fn move_and_write() {
+  let mut canvas = Canvas::new(8, 3).unwrap();
+  let script = [Message::Move { x: 1, y: 0 }, write("hi"), Message::Move { x: 3, y: 2 }, write("there")];
+  assert_eq!(canvas.run(&script), 4);
+  assert_eq!(canvas.to_text(), ".hi.....\n........\n...there\n");
+  assert_eq!(canvas.cursor(), (3, 2));
+}
+
+#[test]
+fn writing_off_the_edges_is_clipped() {
+  let mut canvas = Canvas::new(4, 2).unwrap();
+  canvas.run(&[Message::Move { x: -2, y: 0 }, write("abcdef"), Message::Move { x: 2, y: 1 }, write("xyz\nbelow")]);
+  assert_eq!(canvas.to_text(), "cdef\n..xy\n");
+}
+
+#[test]
+fn newlines_go_back_to_the_cursor_column_and_spaces_erase() {
+  let mut canvas = Canvas::new(5, 3).unwrap();
+  canvas.run(&[Message::Move { x: 1, y: 0 }, write("abc\nde\nf"), write(" \n  ")]);
+  assert_eq!(canvas.to_text(), "..bc.\n.....\n.f...\n");
+}
+
+#[test]
+fn pen_colour_is_kept_per_cell() {
+  let mut canvas = Canvas::new(2, 1).unwrap();
+  canvas.run(&[Message::ChangeColor(Rgb(255, 0, 0)), write("a"), Message::ChangeColor(Rgb(0, 255, 7)), Message::Move { x: 1, y: 0 }, write("b")]);
+  assert_eq!(canvas.pen(), Rgb(0, 255, 7));
+  assert_eq!(canvas.cell(0, 0), Some(Cell { glyph: 'a', color: Rgb(255, 0, 0) }));
+  assert_eq!(canvas.cell(1, 0), Some(Cell { glyph: 'b', color: Rgb(0, 255, 7) }));
+  assert_eq!(canvas.cell(2, 0), None);
+}
+
+#[test]
+fn quit_stops_processing() {
+  let mut canvas = Canvas::new(3, 1).unwrap();
+  let script = [write("a"), Message::Quit, Message::Move { x: 1, y: 0 }, write("b")];
+  assert_eq!(canvas.run(&script), 2);
+  assert!(canvas.is_finished());
+  assert_eq!(canvas.to_text(), "a..\n");
+
+  // and calling a message by hand after that does nothing either
+  write("z").call(&mut canvas);
+  assert_eq!(canvas.to_text(), "a..\n");
+}
+
+#[test]
+fn ppm_output() {
+  let mut canvas = Canvas::new(2, 2).unwrap();
+  canvas.run(&[Message::ChangeColor(Rgb(0, 0, 255)), write("x"), Message::Move { x: 1, y: 1 }, Message::ChangeColor(Rgb(10, 20, 30)), write("y")]);
+  assert_eq!(canvas.to_ppm(), "P3\n2 2\n255\n0 0 255  255 255 255\n255 255 255  10 20 30\n");
+}
+
+#[test]
+fn an_empty_script_leaves_an_empty_canvas() {
+  let mut canvas = Canvas::new(3, 2).unwrap();
+  assert_eq!(canvas.run(&[]), 0);
+  assert_eq!(canvas, Canvas::new(3, 2).unwrap());
+  assert_eq!(canvas.to_text(), "...\n...\n");
+}
+
+#[test]
+fn to_messages_draws_the_same_canvas() {
+  let mut canvas = Canvas::new(6, 3).unwrap();
+  canvas.run(&[write("ab\"c"), Message::ChangeColor(Rgb(1, 2, 3)), Message::Move { x: 2, y: 0 }, write("xy\\\nz"), Message::Move { x: 9, y: -1 }]);
+  let mut again = Canvas::new(6, 3).unwrap();
+  again.run(&canvas.to_messages());
+  assert_eq!(again, canvas);
+
+  canvas.run(&[Message::Quit]);
+  let mut again = Canvas::new(6, 3).unwrap();
+  again.run(&canvas.to_messages());
+  assert_eq!(again, canvas);
+}
+
+#[test]
+fn writing_at_the_far_edge_of_i32_does_not_overflow() {
+  let mut canvas = Canvas::new(2, 2).unwrap();
+  canvas.run(&[Message::Move { x: i32::MAX, y: 0 }, write("ab"), Message::Move { x: 0, y: i32::MAX }, write("cd\nef\ngh")]);
+  canvas.run(&[Message::Move { x: i32::MAX - 1, y: i32::MAX - 1 }, write("abc\ndef\nghi")]);
+  assert_eq!(canvas.to_text(), "..\n..\n");
+  assert_eq!(canvas.cursor(), (i32::MAX - 1, i32::MAX - 1));
+
+  // clipping on the other side still works the same
+  canvas.run(&[Message::Move { x: -1, y: -1 }, write("xyz\nabc")]);
+  assert_eq!(canvas.to_text(), "bc\n..\n");
+}
+
+#[test]
+fn sizes_too_big_to_allocate_are_errors() {
+  // the cell count doesn't fit in a usize at all
+  assert_eq!(Canvas::new(usize::MAX, 2), Err(CanvasError::TooBig { width: usize::MAX, height: 2 }));
+  assert_eq!(Canvas::new(1 << 40, 1 << 40), Err(CanvasError::TooBig { width: 1 << 40, height: 1 << 40 }));
+  // it does, but it's more than a canvas is allowed
+  assert!(Canvas::new(MAX_CELLS + 1, 1).is_err());
+  assert_eq!(Canvas::new(MAX_CELLS, 1).unwrap().width(), MAX_CELLS);
+  // no cells is fine, there's just nowhere to write, but a zero side doesn't let the other be any size
+  assert_eq!(Canvas::new(0, 3).unwrap().to_text(), "");
+  assert!(Canvas::new(0, usize::MAX).is_err());
+}
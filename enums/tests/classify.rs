@@ -0,0 +1,123 @@
+use enums::classify::{AddressClass, MulticastScope};
+use enums::IpAddrKind;
+
+fn class(text: &str) -> AddressClass {
+  text.parse::<IpAddrKind>().unwrap().classify()
+}
+
+// each range by its first and last address, and the address either side of it where that's something else
+#[test]
+fn v4_ranges() {
+  let cases = [
+    ("0.0.0.0", AddressClass::Unspecified),
+    ("0.0.0.1", AddressClass::Reserved),
+    ("0.255.255.255", AddressClass::Reserved),
+    ("127.0.0.0", AddressClass::Loopback),
+    ("127.255.255.255", AddressClass::Loopback),
+    ("10.0.0.0", AddressClass::Private),
+    ("10.255.255.255", AddressClass::Private),
+    ("172.15.255.255", AddressClass::Global),
+    ("172.16.0.0", AddressClass::Private),
+    ("172.31.255.255", AddressClass::Private),
+    ("172.32.0.0", AddressClass::Global),
+    ("192.168.0.0", AddressClass::Private),
+    ("192.168.255.255", AddressClass::Private),
+    ("169.254.0.1", AddressClass::LinkLocal),
+    ("192.0.2.0", AddressClass::Documentation),
+    ("198.51.100.255", AddressClass::Documentation),
+    ("203.0.113.7", AddressClass::Documentation),
+    ("198.18.0.0", AddressClass::Benchmarking),
+    ("198.19.255.255", AddressClass::Benchmarking),
+    ("198.20.0.0", AddressClass::Global),
+    ("100.64.0.0", AddressClass::Reserved),
+    ("100.127.255.255", AddressClass::Reserved),
+    ("100.128.0.0", AddressClass::Global),
+    ("192.0.0.8", AddressClass::Reserved),
+    ("240.0.0.0", AddressClass::Reserved),
+    ("255.255.255.255", AddressClass::Reserved),
+    ("1.1.1.1", AddressClass::Global),
+    ("8.8.8.8", AddressClass::Global),
+  ];
+  for (text, expected) in cases {
+    assert_eq!(class(text), expected, "{text}");
+  }
+}
+
+#[test]
+fn v6_ranges() {
+  let cases = [
+    ("::", AddressClass::Unspecified),
+    ("::1", AddressClass::Loopback),
+    ("::2", AddressClass::Reserved),
+    ("::192.0.2.1", AddressClass::Reserved),
+    ("fc00::", AddressClass::Private),
+    ("fdff:ffff:ffff:ffff:ffff:ffff:ffff:ffff", AddressClass::Private),
+    ("fe80::1", AddressClass::LinkLocal),
+    ("febf:ffff::", AddressClass::LinkLocal),
+    ("fec0::1", AddressClass::Reserved),
+    ("2001:db8::1", AddressClass::Documentation),
+    ("2001:db9::1", AddressClass::Global),
+    ("3fff:fff::", AddressClass::Documentation),
+    ("3fff:1000::", AddressClass::Global),
+    ("2001:2::1", AddressClass::Benchmarking),
+    ("2001:2:1::", AddressClass::Global),
+    ("100::1", AddressClass::Reserved),
+    ("100:0:0:1::", AddressClass::Global),
+    ("2606:4700:4700::1111", AddressClass::Global),
+  ];
+  for (text, expected) in cases {
+    assert_eq!(class(text), expected, "{text}");
+  }
+}
+
+#[test]
+fn multicast_scopes() {
+  let cases = [
+    ("224.0.0.251", MulticastScope::LinkLocal),
+    ("224.0.1.1", MulticastScope::Global),
+    ("239.255.255.250", MulticastScope::SiteLocal),
+    ("239.192.0.1", MulticastScope::OrganizationLocal),
+    ("239.1.2.3", MulticastScope::AdminLocal),
+    ("ff01::1", MulticastScope::InterfaceLocal),
+    ("ff02::fb", MulticastScope::LinkLocal),
+    ("ff03::1", MulticastScope::RealmLocal),
+    ("ff04::1", MulticastScope::AdminLocal),
+    ("ff05::1:3", MulticastScope::SiteLocal),
+    ("ff08::1", MulticastScope::OrganizationLocal),
+    ("ff0e::1", MulticastScope::Global),
+    ("ff16::1", MulticastScope::Other(6)),
+  ];
+  for (text, scope) in cases {
+    assert_eq!(class(text), AddressClass::Multicast(scope), "{text}");
+  }
+}
+
+#[test]
+fn mapped_v4_is_classified_as_v4() {
+  assert_eq!(class("::ffff:127.0.0.1"), AddressClass::Loopback);
+  assert_eq!(class("::ffff:10.1.2.3"), AddressClass::Private);
+  assert_eq!(class("::ffff:224.0.0.1"), AddressClass::Multicast(MulticastScope::LinkLocal));
+  assert_eq!(class("::ffff:8.8.8.8"), AddressClass::Global);
+  assert_eq!(class("::ffff:0.0.0.0"), AddressClass::Unspecified);
+}
+
+#[test]
+fn converting_to_v4() {
+  let address = |text: &str| text.parse::<IpAddrKind>().unwrap();
+  assert_eq!(address("::ffff:192.0.2.1").to_ipv4_mapped(), Some(address("192.0.2.1")));
+  // the compatible form only counts for to_ipv4
+  assert_eq!(address("::192.0.2.1").to_ipv4_mapped(), None);
+  assert_eq!(address("::192.0.2.1").to_ipv4(), Some(address("192.0.2.1")));
+  assert_eq!(address("::1").to_ipv4(), Some(address("0.0.0.1")));
+  assert_eq!(address("10.0.0.1").to_ipv4(), Some(address("10.0.0.1")));
+  assert_eq!(address("2001:db8::1").to_ipv4(), None);
+}
+
+#[test]
+fn loopback_and_global() {
+  let address = |text: &str| text.parse::<IpAddrKind>().unwrap();
+  assert!(address("127.0.0.1").is_loopback() && address("::1").is_loopback() && address("::ffff:127.1.2.3").is_loopback());
+  assert!(!address("::1").is_global());
+  assert!(address("1.1.1.1").is_global() && address("2606:4700::1").is_global());
+  assert!(!address("10.0.0.1").is_global());
+}
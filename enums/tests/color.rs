@@ -0,0 +1,83 @@
+// Rgb against known values: hex, CSS names, HSL/HSV, blending, WCAG contrast, and colours in commands
+use enums::color::{ColorError, Hsl, Hsv, Rgb, MIN_CONTRAST_AA};
+use enums::command::{self, ErrorKind};
+use enums::Message;
+
+#[test]
+fn hex_parse_and_format() {
+  assert_eq!("#ff8800".parse(), Ok(Rgb(255, 136, 0)));
+  assert_eq!("#FF8800".parse(), Ok(Rgb(255, 136, 0)));
+  assert_eq!("#f80".parse(), Ok(Rgb(255, 136, 0)));
+  assert_eq!(Rgb(255, 136, 0).to_string(), "#ff8800");
+  assert_eq!(Rgb(1, 2, 3).to_string(), "#010203");
+  for bad in ["#ff880", "#ff88000", "#", "#ggg", "#+f8", "ff8800"] {
+    assert!(bad.parse::<Rgb>().is_err(), "{bad}");
+  }
+  assert_eq!("#12".parse::<Rgb>(), Err(ColorError::InvalidHex(String::from("#12"))));
+}
+
+#[test]
+fn css_names() {
+  assert_eq!("tomato".parse(), Ok(Rgb(255, 99, 71)));
+  assert_eq!(Rgb::from_name("RebeccaPurple"), Some(Rgb(0x66, 0x33, 0x99)));
+  assert_eq!(Rgb::from_name("grey"), Rgb::from_name("gray"));
+  assert_eq!(Rgb::from_name("transparent"), None);
+  assert_eq!(Rgb(0, 255, 255).name(), Some("aqua"));
+  assert_eq!(Rgb(1, 2, 3).name(), None);
+  assert_eq!("nope".parse::<Rgb>(), Err(ColorError::UnknownName(String::from("nope"))));
+}
+
+#[test]
+fn checked_from_i32s() {
+  assert_eq!(Rgb::try_from((0, 128, 255)), Ok(Rgb(0, 128, 255)));
+  assert_eq!(Rgb::try_from((0, -1, 255)), Err(ColorError::OutOfRange(-1)));
+  assert_eq!(Rgb::try_from((256, 0, 0)), Err(ColorError::OutOfRange(256)));
+}
+
+#[test]
+fn hsl_and_hsv() {
+  assert_eq!(Hsl::from(Rgb(255, 136, 0)), Hsl { hue: 32.0, saturation: 1.0, lightness: 0.5 });
+  assert_eq!(Hsv::from(Rgb(0, 0, 255)), Hsv { hue: 240.0, saturation: 1.0, value: 1.0 });
+  assert_eq!(Hsl::from(Rgb(128, 128, 128)).saturation, 0.0);
+  assert_eq!(Rgb::from(Hsl { hue: 120.0, saturation: 1.0, lightness: 0.25 }), Rgb(0, 128, 0));
+  // hues wrap round and the rest is clamped
+  assert_eq!(Rgb::from(Hsv { hue: -120.0, saturation: 2.0, value: 1.0 }), Rgb(0, 0, 255));
+  // every colour comes back exactly (a sample, all 16 million take a while without --release)
+  for value in (0..1u32 << 24).step_by(997) {
+    let [_, r, g, b] = value.to_be_bytes();
+    let color = Rgb(r, g, b);
+    assert_eq!(Rgb::from(Hsl::from(color)), color);
+    assert_eq!(Rgb::from(Hsv::from(color)), color);
+  }
+}
+
+#[test]
+fn blending() {
+  assert_eq!(Rgb(255, 0, 0).blend(Rgb::WHITE, 0.5), Rgb(255, 128, 128));
+  assert_eq!(Rgb(255, 0, 0).blend(Rgb::BLACK, 1.0), Rgb(255, 0, 0));
+  assert_eq!(Rgb(255, 0, 0).blend(Rgb::BLACK, -1.0), Rgb::BLACK);
+}
+
+#[test]
+fn contrast() {
+  assert!((Rgb::BLACK.contrast_ratio(Rgb::WHITE) - 21.0).abs() < 1e-9);
+  assert_eq!(Rgb(10, 20, 30).contrast_ratio(Rgb(10, 20, 30)), 1.0);
+  // #777 on white is the well known just-misses-AA grey, #767676 just makes it
+  let grey = Rgb(0x77, 0x77, 0x77);
+  assert!((grey.contrast_ratio(Rgb::WHITE) - 4.48).abs() < 0.005);
+  assert!(grey.contrast_ratio(Rgb::WHITE) < MIN_CONTRAST_AA);
+  assert!(Rgb(0x76, 0x76, 0x76).contrast_ratio(Rgb::WHITE) >= MIN_CONTRAST_AA);
+  assert_eq!(Rgb::WHITE.contrast_ratio(grey), grey.contrast_ratio(Rgb::WHITE));
+}
+
+#[test]
+fn colours_in_commands() {
+  assert_eq!(command::parse("color #f80"), Ok(Message::ChangeColor(Rgb(255, 136, 0))));
+  assert_eq!(command::parse("colour Tomato"), Ok(Message::ChangeColor(Rgb(255, 99, 71))));
+  assert_eq!(command::parse("color 1 2 3"), Ok(Message::ChangeColor(Rgb(1, 2, 3))));
+  assert_eq!(command::parse("color 1 256 3").unwrap_err().position, 8);
+  assert_eq!(command::parse("color -1 2 3").unwrap_err().kind, ErrorKind::Expected("R, a whole number from 0 to 255"));
+  let error = command::parse("color #ff").unwrap_err();
+  assert_eq!((error.position, error.kind), (6, ErrorKind::Color(ColorError::InvalidHex(String::from("#ff")))));
+  assert_eq!(command::parse("color red 1").unwrap_err().kind, ErrorKind::TooManyArguments);
+}
@@ -0,0 +1,95 @@
+use enums::color::{ColorError, Rgb};
+use enums::command::{parse, tokenize, ErrorKind, ParseError, Token};
+use enums::Message;
+
+fn error(line: &str) -> (usize, ErrorKind) {
+  let ParseError { position, kind } = parse(line).unwrap_err();
+  (position, kind)
+}
+
+#[test]
+fn each_variant() {
+  assert_eq!(parse("quit"), Ok(Message::Quit));
+  assert_eq!(parse("move -3 4"), Ok(Message::Move { x: -3, y: 4 }));
+  assert_eq!(parse("write \"hello world\""), Ok(Message::Write(String::from("hello world"))));
+  assert_eq!(parse("color 255 0 10"), Ok(Message::ChangeColor(Rgb(255, 0, 10))));
+  assert_eq!(parse("colour tomato"), Ok(Message::ChangeColor(Rgb(255, 99, 71))));
+  // the command word isn't case sensitive and spacing doesn't matter
+  assert_eq!(parse("  MOVE   1\t2  "), Ok(Message::Move { x: 1, y: 2 }));
+}
+
+#[test]
+fn escapes_inside_quotes() {
+  assert_eq!(parse(r#"write "a \"b\" \\ c\nd""#), Ok(Message::Write(String::from("a \"b\" \\ c\nd"))));
+  assert_eq!(parse(r#"write """#), Ok(Message::Write(String::new())));
+}
+
+#[test]
+fn bad_quoting() {
+  // the position of the opening quote
+  assert_eq!(error(r#"write "abc"#), (6, ErrorKind::UnterminatedString));
+  assert_eq!(error(r#"write "abc\"#), (6, ErrorKind::UnterminatedString));
+  // the position of the backslash
+  assert_eq!(error(r#"write "a\qb""#), (8, ErrorKind::InvalidEscape('q')));
+  assert_eq!(error("write hello"), (6, ErrorKind::Expected("\"TEXT\" in quotes")));
+  assert_eq!(error("write"), (5, ErrorKind::Expected("\"TEXT\" in quotes")));
+  // a quote straight after a word starts a new token, which is one too many here
+  assert_eq!(error(r#"write "a"b"#), (9, ErrorKind::TooManyArguments));
+}
+
+#[test]
+fn bad_numbers() {
+  assert_eq!(error("move 99999999999 1"), (5, ErrorKind::NumberOutOfRange));
+  assert_eq!(error("move 1 -2147483649"), (7, ErrorKind::NumberOutOfRange));
+  assert_eq!(error("move 1 x"), (7, ErrorKind::Expected("Y, a whole number")));
+  assert_eq!(error("move 1.5 2"), (5, ErrorKind::Expected("X, a whole number")));
+  // nothing after the 1, the position is the end of the line
+  assert_eq!(error("move 1"), (6, ErrorKind::Expected("Y, a whole number")));
+  assert_eq!(error("color 256 0 0"), (6, ErrorKind::Expected("R, a whole number from 0 to 255")));
+  assert_eq!(error("color 0 -1 0"), (8, ErrorKind::Expected("G, a whole number from 0 to 255")));
+  assert_eq!(error("color 0 0"), (9, ErrorKind::Expected("B, a whole number from 0 to 255")));
+  assert_eq!(error("move 1 2 3"), (9, ErrorKind::TooManyArguments));
+}
+
+#[test]
+fn bad_colours() {
+  assert_eq!(error("color #ggg"), (6, ErrorKind::Color(ColorError::InvalidHex(String::from("#ggg")))));
+  assert_eq!(error("color  blurple"), (7, ErrorKind::Color(ColorError::UnknownName(String::from("blurple")))));
+}
+
+#[test]
+fn unknown_commands() {
+  assert_eq!(error("jump 1 2"), (0, ErrorKind::UnknownCommand(String::from("jump"))));
+  assert_eq!(error("   Jump"), (3, ErrorKind::UnknownCommand(String::from("jump"))));
+  assert_eq!(error("42"), (0, ErrorKind::Expected("a command")));
+  assert_eq!(error("\"quit\""), (0, ErrorKind::Expected("a command")));
+  assert_eq!(error(""), (0, ErrorKind::Empty));
+  assert_eq!(error("   "), (3, ErrorKind::Empty));
+}
+
+#[test]
+fn positions_are_bytes() {
+  // é is two bytes, so the 5 starts at byte 11 and not character 10
+  assert_eq!(error("write \"é\" 5"), (11, ErrorKind::TooManyArguments));
+  assert_eq!(tokenize("write \"é\" 5"), Ok(vec![(0, Token::Word(String::from("write"))), (6, Token::Str(String::from("é"))), (11, Token::Number(5))]));
+}
+
+#[test]
+fn display_parses_back() {
+  let messages = [
+    Message::Quit,
+    Message::Move { x: i32::MIN, y: i32::MAX },
+    Message::Write(String::from("quotes \" backslash \\ newline \n done")),
+    Message::Write(String::new()),
+    Message::ChangeColor(Rgb(0, 128, 255)),
+  ];
+  for message in messages {
+    assert_eq!(parse(&message.to_string()), Ok(message.clone()), "{message}");
+  }
+}
+
+#[test]
+fn error_messages() {
+  assert_eq!(parse("jump").unwrap_err().to_string(), "position 0: unknown command \"jump\", try help");
+  assert_eq!(parse("move 1").unwrap_err().to_string(), "position 6: expected Y, a whole number");
+}
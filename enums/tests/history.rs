@@ -0,0 +1,164 @@
+// undo and redo, looking back at past steps, and rebuilding the canvas from the log after a restart
+use std::{env, fs};
+
+use enums::canvas::Canvas;
+use enums::history::{Entry, History, HistoryError};
+use enums::Message;
+
+fn write(text: &str) -> Message {
+  Message::Write(String::from(text))
+}
+
+fn log_path(name: &str) -> std::path::PathBuf {
+  let path = env::temp_dir().join(format!("enums-history-{name}-{}.log", std::process::id()));
+  let _ = fs::remove_file(&path);
+  let _ = fs::remove_file(path.with_extension("log.snapshot"));
+  path
+}
+
+#[test]
+fn undo_and_redo() {
+  let mut history = History::new(4, 1).unwrap();
+  history.apply(write("ab")).unwrap();
+  history.apply(Message::Move { x: 1, y: 0 }).unwrap();
+  history.apply(write("z")).unwrap();
+  assert_eq!(history.canvas().to_text(), "az..\n");
+
+  assert!(history.undo().unwrap());
+  assert_eq!(history.canvas().to_text(), "ab..\n");
+  assert!(history.undo().unwrap());
+  assert_eq!(history.canvas().cursor(), (0, 0));
+  assert!(history.redo().unwrap());
+  assert_eq!(history.canvas().cursor(), (1, 0));
+  assert!(history.can_redo());
+
+  // something new after an undo means the undone message can't come back
+  history.apply(write("q")).unwrap();
+  assert!(!history.can_redo());
+  assert!(!history.redo().unwrap());
+  assert_eq!(history.canvas().to_text(), "aq..\n");
+  assert_eq!(history.applied(), [write("ab"), Message::Move { x: 1, y: 0 }, write("q")]);
+}
+
+#[test]
+fn undo_takes_back_a_quit() {
+  let mut history = History::new(2, 1).unwrap();
+  history.apply(Message::Quit).unwrap();
+  assert!(history.canvas().is_finished());
+  history.undo().unwrap();
+  history.apply(write("ok")).unwrap();
+  assert_eq!(history.canvas().to_text(), "ok\n");
+  // and undoing everything is the empty canvas, with nothing left to undo
+  while history.undo().unwrap() {}
+  assert_eq!(history.canvas(), &Canvas::new(2, 1).unwrap());
+  assert!(!history.can_undo());
+}
+
+#[test]
+fn every_past_step_can_be_looked_at() {
+  let mut history = History::new(3, 1).unwrap().with_snapshot_every(2);
+  let mut seen = vec![history.canvas().clone()];
+  for x in 0..3 {
+    history.apply(Message::Move { x, y: 0 }).unwrap();
+    seen.push(history.canvas().clone());
+    history.apply(write("#")).unwrap();
+    seen.push(history.canvas().clone());
+  }
+  for _ in 0..3 {
+    history.undo().unwrap();
+    seen.push(history.canvas().clone());
+  }
+  history.redo().unwrap();
+  seen.push(history.canvas().clone());
+
+  assert_eq!(history.entries().len(), seen.len() - 1);
+  for (step, canvas) in seen.iter().enumerate() {
+    assert_eq!(history.state_at(step).as_ref(), Some(canvas), "step {step}");
+  }
+  assert_eq!(history.state_at(seen.len()), None);
+}
+
+#[test]
+fn the_log_survives_a_restart() {
+  let path = log_path("restart");
+  {
+    let mut history = History::new(5, 2).unwrap().with_snapshot_every(3).with_log(&path).unwrap();
+    for (x, text) in ["a", "b", "c", "d", "e"].into_iter().enumerate() {
+      history.apply(Message::Move { x: x as i32, y: 1 }).unwrap();
+      history.apply(write(text)).unwrap();
+    }
+    history.undo().unwrap();
+    history.undo().unwrap();
+  }
+  assert!(fs::read_to_string(&path).unwrap().starts_with("canvas 5 2\nmove 0 1\nwrite \"a\"\n"));
+  assert!(path.with_extension("log.snapshot").exists());
+
+  let mut history = History::new(5, 2).unwrap().with_snapshot_every(3).with_log(&path).unwrap();
+  assert_eq!(history.canvas().to_text(), ".....\nabcd.\n");
+  assert_eq!(history.entries().len(), 12);
+  assert_eq!(history.entries()[11], Entry::Undo);
+  // the undone messages can still be put back
+  history.redo().unwrap();
+  history.redo().unwrap();
+  assert_eq!(history.canvas().to_text(), ".....\nabcde\n");
+  drop(history);
+
+  let history = History::new(5, 2).unwrap().with_log(&path).unwrap();
+  assert_eq!(history.canvas().to_text(), ".....\nabcde\n");
+  fs::remove_file(path.with_extension("log.snapshot")).unwrap();
+  fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_line_cut_off_by_a_crash_is_dropped() {
+  let path = log_path("crash");
+  fs::write(&path, "canvas 3 1\nwrite \"hi\"\nmove 1 0\nwrite \"y").unwrap();
+  let mut history = History::new(3, 1).unwrap().with_log(&path).unwrap();
+  assert_eq!(history.entries().len(), 2);
+  history.apply(write("o")).unwrap();
+  assert_eq!(history.canvas().to_text(), "ho.\n");
+  assert_eq!(fs::read_to_string(&path).unwrap(), "canvas 3 1\nwrite \"hi\"\nmove 1 0\nwrite \"o\"\n");
+  fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn bad_logs_are_errors() {
+  let path = log_path("bad");
+  fs::write(&path, "canvas 3 1\nwrite \"hi\"\n").unwrap();
+  assert!(matches!(History::new(4, 1).unwrap().with_log(&path), Err(HistoryError::Log { line: 1, .. })));
+  fs::write(&path, "canvas 3 1\nwrite \"hi\"\njump 3\nquit\n").unwrap();
+  assert!(matches!(History::new(3, 1).unwrap().with_log(&path), Err(HistoryError::Log { line: 3, .. })));
+  fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_snapshot_that_cant_be_written_doesnt_fail_the_step() {
+  let path = log_path("snapshot-fails");
+  // a directory where the snapshot is written before it's renamed into place
+  let blocked = path.with_extension("log.snapshot.tmp");
+  let _ = fs::remove_dir(&blocked);
+  fs::create_dir(&blocked).unwrap();
+
+  let mut history = History::new(3, 1).unwrap().with_snapshot_every(2).with_log(&path).unwrap();
+  history.apply(write("a")).unwrap();
+  assert!(history.take_snapshot_error().is_none());
+  history.apply(write("b")).unwrap();
+  assert_eq!(history.canvas().to_text(), "b..\n");
+  assert!(matches!(history.take_snapshot_error(), Some(HistoryError::Io(_))));
+  assert!(history.take_snapshot_error().is_none());
+  assert!(history.undo().unwrap());
+  drop(history);
+
+  // the log has every step, and starting again without a snapshot gets to the same place
+  assert_eq!(fs::read_to_string(&path).unwrap(), "canvas 3 1\nwrite \"a\"\nwrite \"b\"\nundo\n");
+  assert!(!path.with_extension("log.snapshot").exists());
+  let history = History::new(3, 1).unwrap().with_log(&path).unwrap();
+  assert_eq!(history.canvas().to_text(), "a..\n");
+  fs::remove_dir(&blocked).unwrap();
+  fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_canvas_too_big_is_an_error() {
+  assert!(matches!(History::new(usize::MAX, 3), Err(HistoryError::Canvas(_))));
+}
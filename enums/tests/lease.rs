@@ -0,0 +1,219 @@
+/*
+  The clock in these tests is a Cell the test moves forward by hand, so "an hour later" takes no
+  time at all and every run sees exactly the same times.
+*/
+use std::cell::Cell;
+use std::rc::Rc;
+use std::{env, fs};
+
+use enums::lease::{LeaseError, LeasePool};
+use enums::network::IpNetwork;
+use enums::IpAddrKind;
+
+fn address(text: &str) -> IpAddrKind {
+  text.parse().unwrap()
+}
+
+fn network(text: &str) -> IpNetwork {
+  text.parse().unwrap()
+}
+
+// a pool of 192.168.0.1 to .6 with 100 second leases, and the clock it reads
+fn pool() -> (LeasePool<impl Fn() -> u64>, Rc<Cell<u64>>) {
+  let now = Rc::new(Cell::new(1_000));
+  let clock = Rc::clone(&now);
+  (LeasePool::new(network("192.168.0.0/29"), 100, move || clock.get()), now)
+}
+
+#[test]
+fn hands_out_addresses_in_order_and_renews() {
+  let (mut pool, now) = pool();
+  let first = pool.request("alice").unwrap();
+  assert_eq!((first.address, first.expires), (address("192.168.0.1"), 1_100));
+  assert_eq!(pool.request("bob").unwrap().address, address("192.168.0.2"));
+
+  now.set(1_050);
+  let renewed = pool.request("alice").unwrap();
+  assert_eq!((renewed.address, renewed.expires), (address("192.168.0.1"), 1_150));
+}
+
+#[test]
+fn exclusions_and_reservations_are_skipped() {
+  let (mut pool, _) = pool();
+  pool.exclude(network("192.168.0.1/32"));
+  pool.exclude(network("192.168.0.4/31"));
+  pool.reserve("printer", address("192.168.0.2")).unwrap();
+
+  assert_eq!(pool.request("alice").unwrap().address, address("192.168.0.3"));
+  assert_eq!(pool.request("bob").unwrap().address, address("192.168.0.6"));
+  assert!(matches!(pool.request("carol"), Err(LeaseError::Exhausted)));
+  // the reserved address was kept back the whole time
+  assert_eq!(pool.request("printer").unwrap().address, address("192.168.0.2"));
+}
+
+#[test]
+fn reservations_are_checked() {
+  let (mut pool, _) = pool();
+  pool.exclude(network("192.168.0.6/32"));
+  assert!(matches!(pool.reserve("a", address("10.0.0.1")), Err(LeaseError::NotInPool(_))));
+  assert!(matches!(pool.reserve("a", address("192.168.0.7")), Err(LeaseError::NotInPool(_))));
+  assert!(matches!(pool.reserve("a", address("192.168.0.6")), Err(LeaseError::Excluded(_))));
+  assert!(matches!(pool.reserve("has space", address("192.168.0.3")), Err(LeaseError::InvalidClientId(_))));
+
+  pool.request("alice").unwrap();
+  assert!(matches!(pool.reserve("bob", address("192.168.0.1")), Err(LeaseError::AddressTaken(_))));
+  pool.reserve("bob", address("192.168.0.3")).unwrap();
+  assert!(matches!(pool.reserve("carol", address("192.168.0.3")), Err(LeaseError::AddressTaken(_))));
+}
+
+#[test]
+fn expired_leases_are_reclaimed() {
+  let (mut pool, now) = pool();
+  pool.request("alice").unwrap();
+  now.set(1_050);
+  pool.request("bob").unwrap();
+
+  now.set(1_100);
+  let reclaimed = pool.reclaim_expired().unwrap();
+  assert_eq!(reclaimed.iter().map(|lease| lease.client_id.as_str()).collect::<Vec<_>>(), ["alice"]);
+  assert!(pool.lease("alice").is_none());
+  assert_eq!(pool.request("carol").unwrap().address, address("192.168.0.1"));
+}
+
+#[test]
+fn an_expired_address_goes_to_someone_else_when_the_pool_is_full() {
+  let (mut pool, now) = pool();
+  for client in ["a", "b", "c", "d", "e", "f"] {
+    pool.request(client).unwrap();
+  }
+  assert!(matches!(pool.request("g"), Err(LeaseError::Exhausted)));
+
+  now.set(1_100);
+  assert_eq!(pool.request("g").unwrap().address, address("192.168.0.1"));
+  assert!(pool.lease("a").is_none());
+}
+
+#[test]
+fn release_frees_the_address() {
+  let (mut pool, _) = pool();
+  pool.request("alice").unwrap();
+  assert_eq!(pool.release("alice").unwrap().unwrap().address, address("192.168.0.1"));
+  assert!(pool.release("alice").unwrap().is_none());
+  assert_eq!(pool.request("bob").unwrap().address, address("192.168.0.1"));
+}
+
+#[test]
+fn leases_survive_a_restart() {
+  let path = env::temp_dir().join(format!("enums-leases-{}.txt", std::process::id()));
+  let _ = fs::remove_file(&path);
+  let now = Rc::new(Cell::new(5_000));
+
+  let clock = Rc::clone(&now);
+  let mut pool = LeasePool::new(network("2001:db8::/126"), 60, move || clock.get()).with_store(&path).unwrap();
+  pool.request("alice").unwrap();
+  pool.request("bob").unwrap();
+  pool.release("alice").unwrap();
+  drop(pool);
+  assert_eq!(fs::read_to_string(&path).unwrap(), "bob 2001:db8::1 5060\n");
+
+  let clock = Rc::clone(&now);
+  let mut pool = LeasePool::new(network("2001:db8::/126"), 60, move || clock.get()).with_store(&path).unwrap();
+  assert_eq!(pool.lease("bob").unwrap().address, address("2001:db8::1"));
+  assert_eq!(pool.request("carol").unwrap().address, address("2001:db8::"));
+
+  fs::write(&path, "bob 10.0.0.1 5060\n").unwrap();
+  let clock = Rc::clone(&now);
+  let error = LeasePool::new(network("2001:db8::/126"), 60, move || clock.get()).with_store(&path).err().unwrap();
+  assert!(matches!(error, LeaseError::BadStoreLine { line: 1, .. }), "{error}");
+  fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_lease_that_never_runs_out() {
+  let now = Rc::new(Cell::new(u64::MAX - 10));
+  let clock = Rc::clone(&now);
+  let mut pool = LeasePool::new(network("192.168.0.0/29"), u64::MAX, move || clock.get());
+  assert_eq!(pool.request("alice").unwrap().expires, u64::MAX);
+  now.set(u64::MAX - 1);
+  assert_eq!(pool.reclaim_expired().unwrap(), []);
+}
+
+#[test]
+fn a_full_v6_pool_runs_out() {
+  // half of a /64 excluded, it jumps straight over 2^63 addresses instead of trying each one
+  let now = Rc::new(Cell::new(1_000));
+  let clock = Rc::clone(&now);
+  let mut pool = LeasePool::new(network("2001:db8::/64"), 100, move || clock.get());
+  pool.exclude(network("2001:db8::/65"));
+  assert_eq!(pool.request("alice").unwrap().address, address("2001:db8:0:0:8000::"));
+  pool.exclude(network("2001:db8:0:0:8000::/65"));
+  assert!(matches!(pool.request("carol"), Err(LeaseError::Exhausted)));
+  // alice's address is excluded now too, so there's nothing to renew it with either
+  assert!(matches!(pool.request("alice"), Err(LeaseError::Exhausted)));
+
+  // and at the very last address of all there's no next one to go on to
+  let clock = Rc::clone(&now);
+  let mut pool = LeasePool::new(network("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/127"), 100, move || clock.get());
+  pool.request("a").unwrap();
+  assert_eq!(pool.request("b").unwrap().address, address("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"));
+  assert!(matches!(pool.request("c"), Err(LeaseError::Exhausted)));
+}
+
+#[test]
+fn a_failed_save_changes_nothing() {
+  let directory = env::temp_dir().join(format!("enums-leases-dir-{}", std::process::id()));
+  let _ = fs::remove_dir_all(&directory);
+  fs::create_dir(&directory).unwrap();
+  let path = directory.join("leases.txt");
+  let now = Rc::new(Cell::new(1_000));
+  let clock = Rc::clone(&now);
+  let mut pool = LeasePool::new(network("192.168.0.0/29"), 100, move || clock.get()).with_store(&path).unwrap();
+  pool.request("alice").unwrap();
+
+  // with the directory gone there's nowhere to save to
+  fs::remove_dir_all(&directory).unwrap();
+  assert!(matches!(pool.request("bob"), Err(LeaseError::Io(_))));
+  assert!(pool.lease("bob").is_none());
+  assert!(matches!(pool.release("alice"), Err(LeaseError::Io(_))));
+  now.set(1_100);
+  assert!(matches!(pool.reclaim_expired(), Err(LeaseError::Io(_))));
+  assert_eq!(pool.lease("alice").unwrap().address, address("192.168.0.1"));
+
+  // once it can save again the pool carries on from where the file left off
+  fs::create_dir(&directory).unwrap();
+  now.set(1_000);
+  assert_eq!(pool.request("bob").unwrap().address, address("192.168.0.2"));
+  assert_eq!(fs::read_to_string(&path).unwrap(), "alice 192.168.0.1 1100\nbob 192.168.0.2 1100\n");
+  fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn an_address_excluded_after_it_was_leased_isnt_renewed() {
+  let (mut pool, now) = pool();
+  pool.request("alice").unwrap();
+  pool.request("bob").unwrap();
+  pool.exclude(network("192.168.0.1/32"));
+  now.set(1_050);
+  let moved = pool.request("alice").unwrap();
+  assert_eq!((moved.address, moved.expires), (address("192.168.0.3"), 1_150));
+  // the old address isn't held by anyone now, and bob's renewal is unaffected
+  assert_eq!(pool.leases().filter(|lease| lease.address == address("192.168.0.1")).count(), 0);
+  assert_eq!(pool.request("bob").unwrap().address, address("192.168.0.2"));
+}
+
+#[test]
+fn a_stored_lease_on_someone_elses_reservation_is_rejected() {
+  let path = env::temp_dir().join(format!("enums-leases-reserved-{}.txt", std::process::id()));
+  fs::write(&path, "bob 192.168.0.2 5060\n").unwrap();
+  let (mut first, _) = pool();
+  first.reserve("printer", address("192.168.0.2")).unwrap();
+  let error = first.with_store(&path).err().unwrap();
+  assert!(matches!(&error, LeaseError::BadStoreLine { line: 1, message } if message.contains("reserved for printer")), "{error}");
+
+  // the client the address is reserved for can have a lease on it in the file
+  let (mut pool, _) = pool();
+  pool.reserve("bob", address("192.168.0.2")).unwrap();
+  let pool = pool.with_store(&path).unwrap();
+  assert_eq!(pool.lease("bob").unwrap().address, address("192.168.0.2"));
+  fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,151 @@
+use enums::network::{IpNetwork, NetworkError};
+use enums::{AddrParseError, IpAddrKind};
+
+fn net(text: &str) -> IpNetwork {
+  text.parse().unwrap()
+}
+
+fn addr(text: &str) -> IpAddrKind {
+  text.parse().unwrap()
+}
+
+#[test]
+fn the_address_is_kept_as_written() {
+  let network = net("10.1.2.3/8");
+  assert_eq!(network.to_string(), "10.1.2.3/8");
+  assert_eq!(network.network(), addr("10.0.0.0"));
+  assert_eq!(network.broadcast(), addr("10.255.255.255"));
+  assert_eq!(network.netmask(), addr("255.0.0.0"));
+  assert_eq!(network.trunc().to_string(), "10.0.0.0/8");
+}
+
+#[test]
+fn v4_slash_0_and_32() {
+  let everything = net("1.2.3.4/0");
+  assert_eq!((everything.network(), everything.broadcast()), (addr("0.0.0.0"), addr("255.255.255.255")));
+  assert_eq!(everything.netmask(), addr("0.0.0.0"));
+  assert_eq!((everything.size(), everything.host_count()), (1 << 32, (1 << 32) - 2));
+  assert!(everything.contains(addr("255.255.255.255")));
+  // a V4 network never holds a V6 address, not even ::/0's worth
+  assert!(!everything.contains(addr("::")));
+
+  let one = net("192.0.2.7/32");
+  assert_eq!((one.network(), one.broadcast()), (addr("192.0.2.7"), addr("192.0.2.7")));
+  assert_eq!(one.netmask(), addr("255.255.255.255"));
+  assert_eq!((one.size(), one.host_count()), (1, 1));
+  assert_eq!(one.hosts().collect::<Vec<_>>(), [addr("192.0.2.7")]);
+  assert!(!one.contains(addr("192.0.2.8")));
+}
+
+#[test]
+fn v4_hosts_skip_network_and_broadcast_except_on_31() {
+  let hosts: Vec<String> = net("192.0.2.0/30").hosts().map(|host| host.to_string()).collect();
+  assert_eq!(hosts, ["192.0.2.1", "192.0.2.2"]);
+  let hosts: Vec<String> = net("192.0.2.0/31").hosts().map(|host| host.to_string()).collect();
+  assert_eq!(hosts, ["192.0.2.0", "192.0.2.1"]);
+  assert_eq!(net("192.0.2.0/24").host_count(), 254);
+}
+
+#[test]
+fn v6_slash_0_and_128() {
+  let everything = net("::/0");
+  assert_eq!(everything.broadcast(), addr("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"));
+  // 2^128 doesn't fit, it stops one short
+  assert_eq!((everything.size(), everything.host_count()), (u128::MAX, u128::MAX));
+  let first: Vec<String> = everything.hosts().take(2).map(|host| host.to_string()).collect();
+  assert_eq!(first, ["::", "::1"]);
+
+  let one = net("2001:db8::1/128");
+  assert_eq!((one.network(), one.broadcast()), (addr("2001:db8::1"), addr("2001:db8::1")));
+  assert_eq!((one.size(), one.host_count()), (1, 1));
+  // the last address of all, there's no next one to stop at
+  let last = net("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128");
+  assert_eq!(last.hosts().count(), 1);
+}
+
+#[test]
+fn v6_uses_every_address() {
+  let network = net("2001:db8::/126");
+  let hosts: Vec<String> = network.hosts().map(|host| host.to_string()).collect();
+  assert_eq!(hosts, ["2001:db8::", "2001:db8::1", "2001:db8::2", "2001:db8::3"]);
+  assert_eq!(net("2001:db8::/64").netmask(), addr("ffff:ffff:ffff:ffff::"));
+}
+
+#[test]
+fn parse_errors() {
+  assert_eq!("10.0.0.0".parse::<IpNetwork>(), Err(NetworkError::MissingPrefix));
+  assert_eq!("10.0.0.0/".parse::<IpNetwork>(), Err(NetworkError::InvalidPrefix { position: 9 }));
+  assert_eq!("10.0.0.0/+8".parse::<IpNetwork>(), Err(NetworkError::InvalidPrefix { position: 9 }));
+  assert_eq!("10.0.0.0/8a".parse::<IpNetwork>(), Err(NetworkError::InvalidPrefix { position: 9 }));
+  assert_eq!("10.0.0.0/1000".parse::<IpNetwork>(), Err(NetworkError::InvalidPrefix { position: 9 }));
+  assert_eq!("10.0.0.0/33".parse::<IpNetwork>(), Err(NetworkError::PrefixTooLong { prefix: 33, max: 32 }));
+  assert_eq!("::/129".parse::<IpNetwork>(), Err(NetworkError::PrefixTooLong { prefix: 129, max: 128 }));
+  assert_eq!("::/256".parse::<IpNetwork>(), Err(NetworkError::InvalidPrefix { position: 3 }));
+  assert_eq!("10.0.0/8".parse::<IpNetwork>(), Err(NetworkError::Address(AddrParseError::V4 { position: 6 })));
+}
+
+#[test]
+fn leading_zeros_in_the_prefix_are_rejected() {
+  assert_eq!("10.0.0.0/08".parse::<IpNetwork>(), Err(NetworkError::InvalidPrefix { position: 9 }));
+  assert_eq!("10.0.0.0/00".parse::<IpNetwork>(), Err(NetworkError::InvalidPrefix { position: 9 }));
+  assert_eq!("2001:db8::/032".parse::<IpNetwork>(), Err(NetworkError::InvalidPrefix { position: 11 }));
+  // a plain 0 is fine
+  assert_eq!(net("10.0.0.0/0").prefix(), 0);
+}
+
+#[test]
+fn contains_networks() {
+  let network = net("10.0.0.0/8");
+  assert!(network.contains_network(&net("10.20.0.0/16")));
+  assert!(network.contains_network(&network));
+  assert!(!network.contains_network(&net("10.0.0.0/7")));
+  assert!(!network.contains_network(&net("11.0.0.0/16")));
+}
+
+#[test]
+fn splitting_and_aggregating_give_the_network_back() {
+  for (text, prefix) in [("10.0.0.0/8", 10), ("192.0.2.0/24", 28), ("192.0.2.0/24", 24), ("1.2.3.4/30", 32), ("2001:db8::/48", 52), ("::/0", 3)] {
+    let network = net(text);
+    let subnets: Vec<IpNetwork> = network.subnets(prefix).unwrap().collect();
+    assert_eq!(subnets.len(), 1 << (prefix - network.prefix()), "{text} into /{prefix}");
+    assert!(subnets.iter().all(|subnet| network.contains_network(subnet)));
+    assert_eq!(IpNetwork::aggregate(&subnets), [network.trunc()], "{text} into /{prefix}");
+  }
+}
+
+#[test]
+fn subnets_in_order() {
+  let subnets: Vec<String> = net("10.0.0.0/8").subnets(10).unwrap().map(|subnet| subnet.to_string()).collect();
+  assert_eq!(subnets, ["10.0.0.0/10", "10.64.0.0/10", "10.128.0.0/10", "10.192.0.0/10"]);
+  // the last /128 of all, like hosts it has to stop without adding one past it
+  assert_eq!(net("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/127").subnets(128).unwrap().count(), 2);
+}
+
+#[test]
+fn subnet_prefix_errors() {
+  let network = net("10.0.0.0/16");
+  assert_eq!(network.subnets(8).err(), Some(NetworkError::PrefixTooShort { prefix: 8, min: 16 }));
+  assert_eq!(network.subnets(33).err(), Some(NetworkError::PrefixTooLong { prefix: 33, max: 32 }));
+}
+
+#[test]
+fn supernets() {
+  assert_eq!(net("10.128.0.0/9").supernet(), Some(net("10.0.0.0/8")));
+  assert_eq!(net("::/0").supernet(), None);
+}
+
+#[test]
+fn aggregate_only_joins_halves_of_the_same_supernet() {
+  let networks = [net("10.0.1.0/24"), net("10.0.2.0/24"), net("10.0.3.0/24")];
+  // 1 and 2 are next to each other but not halves of one /23, 2 and 3 are
+  let aggregated: Vec<String> = IpNetwork::aggregate(&networks).iter().map(|network| network.to_string()).collect();
+  assert_eq!(aggregated, ["10.0.1.0/24", "10.0.2.0/23"]);
+}
+
+#[test]
+fn aggregate_drops_what_is_inside_and_sorts_v4_first() {
+  let networks = [net("2001:db8::/32"), net("10.1.2.3/16"), net("10.1.200.0/24"), net("2001:db8:1::/48"), net("10.0.0.0/16")];
+  let aggregated: Vec<String> = IpNetwork::aggregate(&networks).iter().map(|network| network.to_string()).collect();
+  assert_eq!(aggregated, ["10.0.0.0/15", "2001:db8::/32"]);
+  assert!(IpNetwork::aggregate(&[]).is_empty());
+}
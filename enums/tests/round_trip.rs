@@ -0,0 +1,161 @@
+/*
+  Every address here goes text -> IpAddrKind -> text and IpAddrKind -> std::net::IpAddr -> IpAddrKind,
+  and the standard library's parser and Display are used as the reference: whatever it accepts we
+  accept, whatever it prints we print, whatever it rejects we reject.
+*/
+use std::net::IpAddr;
+
+use enums::{ipv6, AddrParseError, IpAddrKind};
+
+const VALID: &[&str] = &[
+  "0.0.0.0",
+  "255.255.255.255",
+  "127.0.0.1",
+  "10.0.0.1",
+  "192.168.1.254",
+  "1.2.3.4",
+  "100.64.0.0",
+  "224.0.0.1",
+  "::",
+  "::1",
+  "1::",
+  "::ffff:0.0.0.0",
+  "::ffff:192.0.2.1",
+  "::ffff:255.255.255.255",
+  "::192.0.2.1",
+  "64:ff9b::192.0.2.33",
+  "1:2:3:4:5:6:7:8",
+  "1:2:3:4:5:6:7::",
+  "::2:3:4:5:6:7:8",
+  "1:2:3:4:5:6:1.2.3.4",
+  "2001:db8::1",
+  "2001:DB8::1",
+  "2001:0db8:0000:0000:0000:0000:0000:0001",
+  "2001:db8:0:0:1:0:0:1",
+  "2001:db8::1:0:0:1",
+  "2001:0:0:1::1",
+  "fe80::1",
+  "ff02::1:ff00:1",
+  "fd00::",
+  "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff",
+  "0:0:0:0:0:0:0:0",
+  "0::0",
+  "1:0::",
+  "::0:1:0:0:0:0",
+  "0:1:0:0:1:0:0:0",
+  "abcd:ef01:2345:6789:abcd:ef01:2345:6789",
+];
+
+const INVALID: &[&str] = &[
+  "",
+  " ",
+  "1.2.3",
+  "1.2.3.4.5",
+  "256.0.0.0",
+  "1.2.3.-4",
+  "01.2.3.4",
+  "1..2.3",
+  "1.2.3.4 ",
+  " 1.2.3.4",
+  "localhost",
+  ":",
+  ":::",
+  "1:::2",
+  "1::2::3",
+  ":1::",
+  "1::2:",
+  "1:",
+  "12345::",
+  "::g",
+  "1:2:3:4:5:6:7",
+  "1:2:3:4:5:6:7:8:9",
+  "1:2:3:4:5:6:7:8::",
+  "::1:2:3:4:5:6:7:8",
+  "1:2:3:4:5:6:7:1.2.3.4",
+  "::1.2.3",
+  "::1.2.3.4.5",
+  "::01.2.3.4",
+  "::256.1.1.1",
+  "::1.2.3.4:5",
+  "::ffff:1.2.3.",
+  "1.2.3.4::",
+];
+
+fn round_trip(text: &str) {
+  let ours: IpAddrKind = text.parse().unwrap_or_else(|error| panic!("{text}: {error}"));
+  let theirs: IpAddr = text.parse().unwrap();
+  assert_eq!(IpAddr::from(ours), theirs, "{text}");
+  assert_eq!(IpAddrKind::from(theirs), ours, "{text}");
+  assert_eq!(ours.to_string(), theirs.to_string(), "{text}");
+  assert_eq!(ours.to_string().parse::<IpAddrKind>(), Ok(ours), "{text}");
+}
+
+#[test]
+fn valid_addresses_round_trip() {
+  for text in VALID {
+    round_trip(text);
+  }
+}
+
+#[test]
+fn invalid_addresses_are_rejected() {
+  for text in INVALID {
+    assert!(text.parse::<IpAddrKind>().is_err(), "{text}");
+    assert!(text.parse::<IpAddr>().is_err(), "{text} is valid to std");
+  }
+}
+
+#[test]
+fn every_octet_value_round_trips() {
+  for value in 0..=255u8 {
+    for position in 0..4 {
+      let mut octets = [0; 4];
+      octets[position] = value;
+      round_trip(&format!("{}.{}.{}.{}", octets[0], octets[1], octets[2], octets[3]));
+    }
+  }
+}
+
+// every combination of zero and non-zero segments, so every place and length `::` can end up
+#[test]
+fn every_zero_run_round_trips() {
+  for mask in 0..=255u8 {
+    let segments: Vec<String> =
+      (0..8).map(|i| if mask & (1 << i) == 0 { String::from("0") } else { format!("{:x}", 0x1111 * (i + 1)) }).collect();
+    round_trip(&segments.join(":"));
+  }
+}
+
+#[test]
+fn canonical_form() {
+  let canonical = |text: &str| text.parse::<IpAddrKind>().unwrap().to_string();
+  assert_eq!(canonical("2001:0DB8:0000:0000:0000:0000:0000:0001"), "2001:db8::1");
+  // the longest run wins, the first one on a tie, and a single 0 is left alone
+  assert_eq!(canonical("2001:0:0:1:0:0:0:1"), "2001:0:0:1::1");
+  assert_eq!(canonical("2001:db8:0:0:1:0:0:1"), "2001:db8::1:0:0:1");
+  assert_eq!(canonical("2001:db8:0:1:1:1:1:1"), "2001:db8:0:1:1:1:1:1");
+  assert_eq!(canonical("0:0:0:0:0:ffff:c000:0201"), "::ffff:192.0.2.1");
+}
+
+#[test]
+fn errors_say_where() {
+  assert_eq!("1.2.300.4".parse::<IpAddrKind>(), Err(AddrParseError::V4 { position: 4 }));
+  assert_eq!(
+    "2001:db8::g".parse::<IpAddrKind>(),
+    Err(AddrParseError::V6(ipv6::ParseError { position: 10, kind: ipv6::ErrorKind::UnexpectedChar('g') }))
+  );
+  assert_eq!(
+    "1::2::3".parse::<IpAddrKind>(),
+    Err(AddrParseError::V6(ipv6::ParseError { position: 4, kind: ipv6::ErrorKind::SecondDoubleColon }))
+  );
+  assert_eq!("fe80::1%eth0".parse::<IpAddrKind>(), Err(AddrParseError::Zone { position: 7 }));
+}
+
+#[test]
+fn zones_are_kept_by_ipv6_parse() {
+  let (segments, zone) = ipv6::parse("fe80::1%eth0").unwrap();
+  assert_eq!(segments, [0xfe80, 0, 0, 0, 0, 0, 0, 1]);
+  assert_eq!(zone, Some("eth0"));
+  assert_eq!(ipv6::format_with_zone(&segments, zone), "fe80::1%eth0");
+  assert_eq!(ipv6::parse("fe80::1%").unwrap_err().kind, ipv6::ErrorKind::EmptyZone);
+}
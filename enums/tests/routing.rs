@@ -0,0 +1,146 @@
+use std::env;
+
+use enums::network::IpNetwork;
+use enums::routing::{LoadError, RoutingTable};
+use enums::IpAddrKind;
+
+fn address(text: &str) -> IpAddrKind {
+  text.parse().unwrap()
+}
+
+fn network(text: &str) -> IpNetwork {
+  text.parse().unwrap()
+}
+
+// the matched network as text and the route, for comparing in one assert
+fn lookup<'a>(table: &'a RoutingTable<&str>, text: &str) -> Option<(String, &'a str)> {
+  table.lookup(address(text)).map(|(network, &route)| (network.to_string(), route))
+}
+
+fn overlapping() -> RoutingTable<&'static str> {
+  let mut table = RoutingTable::new();
+  for (text, route) in [("0.0.0.0/0", "default"), ("10.0.0.0/8", "ten"), ("10.1.0.0/16", "ten-one"), ("10.1.2.0/24", "ten-one-two"), ("10.1.2.3/32", "host")] {
+    table.insert(network(text), route);
+  }
+  table
+}
+
+#[test]
+fn the_longest_prefix_wins() {
+  let table = overlapping();
+  assert_eq!(lookup(&table, "10.1.2.3"), Some((String::from("10.1.2.3/32"), "host")));
+  assert_eq!(lookup(&table, "10.1.2.4"), Some((String::from("10.1.2.0/24"), "ten-one-two")));
+  assert_eq!(lookup(&table, "10.1.3.4"), Some((String::from("10.1.0.0/16"), "ten-one")));
+  assert_eq!(lookup(&table, "10.2.0.0"), Some((String::from("10.0.0.0/8"), "ten")));
+  assert_eq!(lookup(&table, "192.0.2.1"), Some((String::from("0.0.0.0/0"), "default")));
+}
+
+#[test]
+fn v4_and_v6_are_separate() {
+  let mut table = overlapping();
+  // a V4 default route doesn't catch V6 addresses
+  assert_eq!(lookup(&table, "2001:db8::1"), None);
+  table.insert(network("::/0"), "v6-default");
+  table.insert(network("2001:db8::/32"), "doc");
+  assert_eq!(lookup(&table, "2001:db8::1"), Some((String::from("2001:db8::/32"), "doc")));
+  assert_eq!(lookup(&table, "::1"), Some((String::from("::/0"), "v6-default")));
+  // nor the other way round, ::ffff:10.1.2.3 is a V6 address
+  assert_eq!(lookup(&table, "::ffff:10.1.2.3"), Some((String::from("::/0"), "v6-default")));
+}
+
+#[test]
+fn withdrawing_a_route_falls_back_to_the_next_longest() {
+  let mut table = overlapping();
+  assert_eq!(table.remove(&network("10.1.2.0/24")), Some("ten-one-two"));
+  assert_eq!(lookup(&table, "10.1.2.4"), Some((String::from("10.1.0.0/16"), "ten-one")));
+  // the /32 under it is still there
+  assert_eq!(lookup(&table, "10.1.2.3"), Some((String::from("10.1.2.3/32"), "host")));
+  assert_eq!(table.remove(&network("10.1.2.0/24")), None);
+  assert_eq!(table.remove(&network("10.1.2.0/25")), None);
+  assert_eq!(table.len(), 4);
+}
+
+#[test]
+fn host_bits_are_ignored_and_inserting_again_replaces() {
+  let mut table = RoutingTable::new();
+  assert_eq!(table.insert(network("10.1.2.3/8"), "first"), None);
+  assert_eq!(table.insert(network("10.0.0.0/8"), "second"), Some("first"));
+  assert_eq!(table.len(), 1);
+  assert_eq!(table.get(&network("10.9.9.9/8")), Some(&"second"));
+  assert_eq!(table.get(&network("10.0.0.0/9")), None);
+}
+
+#[test]
+fn an_empty_table_finds_nothing() {
+  let table: RoutingTable<&str> = RoutingTable::new();
+  assert!(table.is_empty());
+  assert_eq!(lookup(&table, "10.0.0.1"), None);
+  assert!(table.routes().is_empty());
+}
+
+#[test]
+fn lookup_matches_checking_every_route() {
+  // every network in 192.0.2.0/24 whose first address is a multiple of 37, at every prefix from 20 to 32
+  let mut table = RoutingTable::new();
+  let mut routes = Vec::new();
+  for last in (0..=255u8).step_by(37) {
+    for prefix in 20..=32 {
+      let network = IpNetwork::new(IpAddrKind::V4(192, 0, 2, last), prefix).unwrap().trunc();
+      table.insert(network, network.to_string());
+      routes.push(network);
+    }
+  }
+  for last in 0..=255 {
+    let address = IpAddrKind::V4(192, 0, 2, last);
+    let expected = routes.iter().filter(|network| network.contains(address)).max_by_key(|network| network.prefix());
+    let found = table.lookup(address).map(|(network, route)| (network, route.clone()));
+    assert_eq!(found, expected.map(|&network| (network, network.to_string())), "{address}");
+  }
+}
+
+#[test]
+fn routes_come_out_in_order() {
+  let mut table = overlapping();
+  table.insert(network("2001:db8::/32"), "doc");
+  table.insert(network("9.0.0.0/8"), "nine");
+  let listed: Vec<String> = table.routes().iter().map(|(network, route)| format!("{network} {route}")).collect();
+  assert_eq!(
+    listed,
+    ["0.0.0.0/0 default", "9.0.0.0/8 nine", "10.0.0.0/8 ten", "10.1.0.0/16 ten-one", "10.1.2.0/24 ten-one-two", "10.1.2.3/32 host", "2001:db8::/32 doc"]
+  );
+}
+
+#[test]
+fn parsing_a_routing_file() {
+  let text = "\
+# the default route
+0.0.0.0/0      192.168.1.1
+
+10.0.0.0/8     10.255.255.254   # the office
+10.0.0.0/8     10.255.255.253
+2001:db8::/32  fe80::1
+";
+  let table = RoutingTable::parse(text).unwrap();
+  assert_eq!(table.len(), 3);
+  // listed twice, the last one counts
+  assert_eq!(table.lookup(address("10.1.1.1")), Some((network("10.0.0.0/8"), &address("10.255.255.253"))));
+  assert_eq!(table.lookup(address("2001:db8::5")), Some((network("2001:db8::/32"), &address("fe80::1"))));
+}
+
+#[test]
+fn parse_errors_give_the_line() {
+  let line = |text: &str| match RoutingTable::parse(text) {
+    Err(LoadError::Line { line, .. }) => line,
+    other => panic!("expected a line error, found {:?}", other.map(|table| table.len())),
+  };
+  assert_eq!(line("0.0.0.0/0 1.1.1.1\n10.0.0.0/8\n"), 2);
+  assert_eq!(line("\n\n10.0.0.0/8 1.1.1.1 extra\n"), 3);
+  assert_eq!(line("10.0.0.0/33 1.1.1.1\n"), 1);
+  assert_eq!(line("10.0.0.0/8 1.1.1\n"), 1);
+}
+
+#[test]
+fn loading_a_missing_file() {
+  let path = env::temp_dir().join(format!("enums-routes-missing-{}.txt", std::process::id()));
+  assert!(matches!(RoutingTable::load(&path), Err(LoadError::Io(_))));
+}
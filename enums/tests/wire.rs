@@ -0,0 +1,197 @@
+use enums::color::Rgb;
+use enums::wire::{decode, encode, write_message, Decoder, MessageReader, WireError, MAX_FRAME};
+use enums::Message;
+
+fn messages() -> Vec<Message> {
+  vec![
+    Message::Quit,
+    Message::Move { x: 3, y: 4 },
+    Message::Move { x: i32::MIN, y: i32::MAX },
+    Message::Write(String::new()),
+    Message::Write(String::from("héllo\n")),
+    Message::Write("x".repeat(300)),
+    Message::ChangeColor(Rgb(0, 128, 255)),
+  ]
+}
+
+// the error from decoding one whole frame
+fn error(frame: &[u8]) -> WireError {
+  match decode(frame) {
+    Err(error) => error,
+    other => panic!("expected an error from {frame:?}, found {other:?}"),
+  }
+}
+
+#[test]
+fn round_trip() {
+  for message in messages() {
+    let frame = encode(&message).unwrap();
+    assert_eq!(decode(&frame).unwrap(), Some((message.clone(), frame.len())), "{message}");
+  }
+}
+
+#[test]
+fn the_example_from_the_docs() {
+  assert_eq!(encode(&Message::Move { x: 3, y: 4 }).unwrap(), [3, 1, 6, 8]);
+  assert_eq!(encode(&Message::Move { x: -1, y: 1 }).unwrap(), [3, 1, 1, 2]);
+  assert_eq!(encode(&Message::Quit).unwrap(), [1, 0]);
+}
+
+#[test]
+fn an_incomplete_frame_needs_more() {
+  let frame = encode(&Message::Write(String::from("hello"))).unwrap();
+  for end in 0..frame.len() {
+    assert!(matches!(decode(&frame[..end]), Ok(None)), "{end} bytes");
+  }
+}
+
+#[test]
+fn truncated() {
+  // a frame with no tag in it
+  assert!(matches!(error(&[0]), WireError::Truncated));
+  // move without its y
+  assert!(matches!(error(&[2, 1, 6]), WireError::Truncated));
+  // write says 5 bytes of text but the frame ends after 1
+  assert!(matches!(error(&[3, 2, 5, b'a']), WireError::Truncated));
+  assert!(matches!(error(&[3, 3, 1, 2]), WireError::Truncated));
+}
+
+#[test]
+fn unknown_tag() {
+  assert!(matches!(error(&[1, 9]), WireError::UnknownTag(9)));
+}
+
+#[test]
+fn invalid_utf8() {
+  assert!(matches!(error(&[4, 2, 2, 0xff, 0xfe]), WireError::InvalidUtf8(_)));
+}
+
+#[test]
+fn varint_overflow() {
+  // the largest varint a u32 takes is i32::MIN zigzagged, one bit more is too many
+  assert_eq!(decode(&[7, 1, 0xff, 0xff, 0xff, 0xff, 0x0f, 0]).unwrap(), Some((Message::Move { x: i32::MIN, y: 0 }, 8)));
+  assert!(matches!(error(&[6, 1, 0xff, 0xff, 0xff, 0xff, 0x1f]), WireError::VarintOverflow));
+  // in the frame length too
+  assert!(matches!(error(&[0x80, 0x80, 0x80, 0x80, 0x10]), WireError::VarintOverflow));
+}
+
+#[test]
+fn frame_too_large() {
+  // MAX_FRAME + 1 as a varint, refused before any of the payload arrives
+  assert_eq!(MAX_FRAME + 1, 0x10_0001);
+  assert!(matches!(error(&[0x81, 0x80, 0x40]), WireError::FrameTooLarge(length) if length == MAX_FRAME + 1));
+  // exactly MAX_FRAME is fine, it's still waiting for the bytes
+  assert!(matches!(decode(&[0x80, 0x80, 0x40]), Ok(None)));
+}
+
+#[test]
+fn encode_wont_make_a_frame_the_other_end_refuses() {
+  // the tag and a 3 byte length go in front of the text, so this much text fills a frame exactly
+  let longest = MAX_FRAME - 4;
+  let frame = encode(&Message::Write("x".repeat(longest))).unwrap();
+  assert_eq!(decode(&frame).unwrap().map(|(_, used)| used), Some(frame.len()));
+
+  let one_over = Message::Write("x".repeat(longest + 1));
+  assert!(matches!(encode(&one_over), Err(WireError::FrameTooLarge(length)) if length == MAX_FRAME + 1));
+  assert!(matches!(encode(&Message::Write("x".repeat(MAX_FRAME + 1))), Err(WireError::FrameTooLarge(_))));
+
+  // and nothing is written
+  let mut bytes = Vec::new();
+  let error = write_message(&mut bytes, &one_over).unwrap_err();
+  assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+  assert!(bytes.is_empty());
+}
+
+#[test]
+fn trailing_bytes() {
+  assert!(matches!(error(&[2, 0, 9]), WireError::TrailingBytes(1)));
+  assert!(matches!(error(&[7, 3, 1, 2, 3, 4, 5, 6]), WireError::TrailingBytes(3)));
+}
+
+#[test]
+fn the_decoder_takes_a_byte_at_a_time() {
+  let mut bytes = Vec::new();
+  for message in messages() {
+    bytes.extend(encode(&message).unwrap());
+  }
+  let mut decoder = Decoder::new();
+  let mut decoded = Vec::new();
+  for &byte in &bytes {
+    decoder.feed(&[byte]);
+    while let Some(message) = decoder.next_message().unwrap() {
+      decoded.push(message);
+    }
+  }
+  assert_eq!(decoded, messages());
+  assert_eq!(decoder.pending(), 0);
+}
+
+#[test]
+fn the_decoder_takes_a_lot_at_once() {
+  let mut bytes = Vec::new();
+  for _ in 0..1_000 {
+    bytes.extend(encode(&Message::Move { x: 3, y: 4 }).unwrap());
+  }
+  // with the start of the next frame on the end
+  bytes.extend([3, 1]);
+  let mut decoder = Decoder::new();
+  decoder.feed(&bytes);
+  let mut count = 0;
+  while let Some(message) = decoder.next_message().unwrap() {
+    assert_eq!(message, Message::Move { x: 3, y: 4 });
+    count += 1;
+  }
+  assert_eq!((count, decoder.pending()), (1_000, 2));
+  decoder.feed(&[6, 8]);
+  assert_eq!(decoder.next_message().unwrap(), Some(Message::Move { x: 3, y: 4 }));
+  assert_eq!(decoder.pending(), 0);
+}
+
+#[test]
+fn the_decoder_carries_on_after_a_bad_frame() {
+  let mut decoder = Decoder::new();
+  decoder.feed(&[1, 9]);
+  decoder.feed(&[4, 2, 2, 0xff, 0xfe]);
+  decoder.feed(&encode(&Message::Quit).unwrap());
+  assert!(matches!(decoder.next_message(), Err(WireError::UnknownTag(9))));
+  assert!(matches!(decoder.next_message(), Err(WireError::InvalidUtf8(_))));
+  assert_eq!(decoder.next_message().unwrap(), Some(Message::Quit));
+  assert_eq!(decoder.next_message().unwrap(), None);
+}
+
+#[test]
+fn a_bad_length_stays_an_error() {
+  let mut decoder = Decoder::new();
+  decoder.feed(&[0x81, 0x80, 0x40]);
+  decoder.feed(&encode(&Message::Quit).unwrap());
+  for _ in 0..2 {
+    assert!(matches!(decoder.next_message(), Err(WireError::FrameTooLarge(_))));
+  }
+  assert_eq!(decoder.pending(), 5);
+}
+
+#[test]
+fn reading_and_writing() {
+  let mut bytes = Vec::new();
+  write_message(&mut bytes, &Message::Quit).unwrap();
+  bytes.extend([1, 9]);
+  write_message(&mut bytes, &Message::Move { x: 3, y: 4 }).unwrap();
+  // half of a frame at the end
+  bytes.extend([3, 1]);
+  let mut reader = MessageReader::new(bytes.as_slice());
+  assert_eq!(reader.next().unwrap().unwrap(), Message::Quit);
+  assert!(matches!(reader.next(), Some(Err(WireError::UnknownTag(9)))));
+  assert_eq!(reader.next().unwrap().unwrap(), Message::Move { x: 3, y: 4 });
+  assert!(matches!(reader.next(), Some(Err(WireError::Truncated))));
+  assert!(reader.next().is_none());
+}
+
+#[test]
+fn reading_everything() {
+  let mut bytes = Vec::new();
+  for message in messages() {
+    write_message(&mut bytes, &message).unwrap();
+  }
+  let read: Vec<Message> = MessageReader::new(bytes.as_slice()).map(Result::unwrap).collect();
+  assert_eq!(read, messages());
+}
@@ -0,0 +1,97 @@
+/*
+	cargo bench --bench spatial_index
+	Compares the quadtree against just looping over every rectangle and calling contains_point /
+	intersects on each one. harness = false in Cargo.toml means this is a plain program with its
+	own main, timed with std::time::Instant so no extra crates are needed.
+*/
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rectangles::spatial::QuadTree;
+use rectangles::{PositionedRectangle, Rectangle};
+
+const WORLD: u32 = 100_000;
+const RECTANGLES: usize = 20_000;
+const QUERIES: usize = 2_000;
+
+// a tiny linear congruential generator, the numbers only need to look random and be the same every run
+struct Lcg(u64);
+
+impl Lcg {
+	fn next(&mut self, below: u32) -> u32 {
+		self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+		((self.0 >> 33) % u64::from(below)) as u32
+	}
+}
+
+fn time(label: &str, run: impl Fn() -> usize) -> Duration {
+	let start = Instant::now();
+	let checksum = black_box(run());
+	let elapsed = start.elapsed();
+	println!("{label:<24} {elapsed:>12.2?}  (checksum {checksum})");
+	elapsed
+}
+
+fn main() {
+	let mut rng = Lcg(42);
+	let rects: Vec<PositionedRectangle> = (0..RECTANGLES)
+		.map(|_| {
+			let size = Rectangle::new(1 + rng.next(500), 1 + rng.next(500));
+			PositionedRectangle::new(rng.next(WORLD - 500), rng.next(WORLD - 500), size)
+		})
+		.collect();
+	let points: Vec<(u32, u32)> = (0..QUERIES).map(|_| (rng.next(WORLD), rng.next(WORLD))).collect();
+	let regions: Vec<PositionedRectangle> = (0..QUERIES)
+		.map(|_| PositionedRectangle::new(rng.next(WORLD - 2_000), rng.next(WORLD - 2_000), Rectangle::square(2_000)))
+		.collect();
+
+	let start = Instant::now();
+	let mut tree = QuadTree::new(PositionedRectangle::new(0, 0, Rectangle::square(WORLD)));
+	for rect in &rects {
+		tree.insert(*rect);
+	}
+	println!("{RECTANGLES} rectangles, {QUERIES} queries of each kind");
+	println!("{:<24} {:>12.2?}", "quadtree build", start.elapsed());
+
+	println!();
+	let linear = time("linear point query", || {
+		points
+			.iter()
+			.map(|&(x, y)| rects.iter().filter(|rect| rect.contains_point(x, y)).count())
+			.sum()
+	});
+	let indexed = time("quadtree point query", || points.iter().map(|&(x, y)| tree.query_point(x, y).len()).sum());
+	println!("speed up {:.1}x", linear.as_secs_f64() / indexed.as_secs_f64());
+
+	println!();
+	let linear = time("linear range query", || {
+		regions
+			.iter()
+			.map(|region| rects.iter().filter(|rect| rect.intersects(region)).count())
+			.sum()
+	});
+	let indexed = time("quadtree range query", || regions.iter().map(|region| tree.query_range(region).len()).sum());
+	println!("speed up {:.1}x", linear.as_secs_f64() / indexed.as_secs_f64());
+
+	println!();
+	let linear = time("linear nearest", || {
+		points
+			.iter()
+			.filter_map(|&(x, y)| (0..rects.len()).min_by_key(|&id| rects[id].distance_squared_to_point(x, y)))
+			.sum()
+	});
+	let indexed = time("quadtree nearest", || points.iter().filter_map(|&(x, y)| tree.nearest(x, y)).sum());
+	println!("speed up {:.1}x", linear.as_secs_f64() / indexed.as_secs_f64());
+
+	// the two approaches have to agree or the timings mean nothing
+	for &(x, y) in &points {
+		let expected: Vec<usize> = (0..rects.len()).filter(|&id| rects[id].contains_point(x, y)).collect();
+		assert_eq!(tree.query_point(x, y), expected);
+		let closest = (0..rects.len()).min_by_key(|&id| rects[id].distance_squared_to_point(x, y));
+		assert_eq!(tree.nearest(x, y), closest);
+	}
+	for region in &regions {
+		let expected: Vec<usize> = (0..rects.len()).filter(|&id| rects[id].intersects(region)).collect();
+		assert_eq!(tree.query_range(region), expected);
+	}
+}
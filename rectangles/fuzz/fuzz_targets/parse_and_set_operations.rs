@@ -0,0 +1,68 @@
+/*
+	cargo install cargo-fuzz (needs a nightly toolchain), then from the rectangles directory:
+		cargo +nightly fuzz run parse_and_set_operations
+	The input is used twice: as text for the parser, and as raw bytes cut into 16 byte chunks
+	that become positioned rectangles (x, y, width, height as little endian u32s) for the set
+	operations. Nothing may panic and the asserts are the same invariants the property tests check.
+*/
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rectangles::parse::parse_rectangles;
+use rectangles::spatial::QuadTree;
+use rectangles::{coverage, free_space, PositionedRectangle, Rectangle};
+
+fn word(bytes: &[u8]) -> u32 {
+	u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fuzz_target!(|data: &[u8]| {
+	if let Ok(rects) = parse_rectangles(&String::from_utf8_lossy(data)) {
+		// whatever parsed has to print and parse back to the same thing
+		let text: String = rects.iter().map(|rect| format!("{rect}\n")).collect();
+		assert_eq!(parse_rectangles(&text).as_ref(), Ok(&rects));
+		if let Some((container, items)) = rects.split_first() {
+			let _ = container.pack(items, rectangles::packing::Heuristic::MaxRects, true);
+		}
+	}
+
+	// the sweep line and the quadtree are O(n log n), the pairwise checks O(n²), so keep it small
+	let positioned: Vec<PositionedRectangle> = data
+		.chunks_exact(16)
+		.take(64)
+		.map(|chunk| PositionedRectangle::new(word(chunk), word(&chunk[4..]), Rectangle::new(word(&chunk[8..]), word(&chunk[12..]))))
+		.collect();
+
+	for a in &positioned {
+		for b in &positioned {
+			assert_eq!(a.intersection(b), b.intersection(a));
+			if let Some(common) = a.intersection(b) {
+				assert!(a.contains(&common) && b.contains(&common));
+			}
+		}
+	}
+
+	let measured = coverage::measure(&positioned);
+	let largest = positioned.iter().map(|rect| u128::from(rect.area())).max().unwrap_or(0);
+	assert!(measured.union_area >= largest);
+	assert!(measured.union_area <= measured.total_area);
+	assert!(measured.overlap_area <= measured.union_area);
+
+	if let Some((bounds, obstacles)) = positioned.split_first() {
+		let uncovered = coverage::uncovered_area(bounds, obstacles);
+		assert!(uncovered <= u128::from(bounds.area()));
+		if let Some(free) = free_space::largest_empty_area(bounds, obstacles) {
+			assert!(obstacles.iter().all(|obstacle| !obstacle.intersects(&free)));
+		}
+
+		let mut tree = QuadTree::new(*bounds);
+		let ids: Vec<usize> = obstacles.iter().map(|rect| tree.insert(*rect)).collect();
+		for (rect, &id) in obstacles.iter().zip(&ids) {
+			assert!(tree.query_range(rect).contains(&id) || rect.area() == 0);
+		}
+		for id in ids {
+			assert!(tree.remove(id).is_some());
+		}
+		assert!(tree.is_empty());
+	}
+});
@@ -0,0 +1,144 @@
+/*
+	Adding up area_with_struct (or Rectangle::area) for a pile of rectangles counts every spot
+	where two of them overlap twice. This works out what is really covered.
+	It is a sweep line: walk from left to right over the x coordinates where a rectangle starts
+	or ends, and between two of those stops the covered height doesn't change, so the area of
+	that slice is just its width times the covered height. The covered height is kept in a
+	segment tree over the y coordinates (only the y values that actually appear, that is the
+	coordinate compression part) so each rectangle edge costs O(log n).
+	Everything is u128: a single u32 by u32 area still fits in a u64 but adding a few of those
+	together doesn't.
+*/
+use crate::PositionedRectangle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Coverage {
+	// every rectangle's area added up, overlaps counted as many times as they are covered
+	pub total_area: u128,
+	// the area covered by at least one rectangle
+	pub union_area: u128,
+	// the area covered by two or more rectangles
+	pub overlap_area: u128,
+}
+
+pub fn measure(rects: &[PositionedRectangle]) -> Coverage {
+	let total_area = rects.iter().map(|rect| u128::from(rect.area())).sum();
+
+	// the distinct y values, the segment tree leaves are the gaps between neighbouring ones
+	let mut ys: Vec<u64> = rects
+		.iter()
+		.filter(|rect| rect.area() > 0)
+		.flat_map(|rect| [u64::from(rect.y), rect.bottom()])
+		.collect();
+	ys.sort_unstable();
+	ys.dedup();
+	if ys.len() < 2 {
+		return Coverage { total_area, ..Coverage::default() };
+	}
+
+	// (x, +1 for a left edge or -1 for a right edge, top, bottom)
+	let mut edges: Vec<(u64, i32, u64, u64)> = rects
+		.iter()
+		.filter(|rect| rect.area() > 0)
+		.flat_map(|rect| {
+			[
+				(u64::from(rect.x), 1, u64::from(rect.y), rect.bottom()),
+				(rect.right(), -1, u64::from(rect.y), rect.bottom()),
+			]
+		})
+		.collect();
+	edges.sort_unstable();
+
+	let mut tree = SegmentTree::new(ys);
+	let mut union_area = 0;
+	let mut overlap_area = 0;
+	let mut last_x = edges[0].0;
+	for (x, delta, top, bottom) in edges {
+		let width = u128::from(x - last_x);
+		union_area += width * u128::from(tree.covered_once());
+		overlap_area += width * u128::from(tree.covered_twice());
+		tree.add(top, bottom, delta);
+		last_x = x;
+	}
+
+	Coverage { total_area, union_area, overlap_area }
+}
+
+pub fn union_area(rects: &[PositionedRectangle]) -> u128 {
+	measure(rects).union_area
+}
+
+pub fn overlap_area(rects: &[PositionedRectangle]) -> u128 {
+	measure(rects).overlap_area
+}
+
+// how much of bounds none of the rectangles cover, the parts of rectangles outside bounds are ignored
+pub fn uncovered_area(bounds: &PositionedRectangle, rects: &[PositionedRectangle]) -> u128 {
+	let clipped: Vec<PositionedRectangle> = rects.iter().filter_map(|rect| rect.intersection(bounds)).collect();
+	u128::from(bounds.area()) - union_area(&clipped)
+}
+
+/*
+	Each node covers a range of the compressed y values and remembers how many rectangles cover
+	that whole range right now (count) plus how much of the range is covered at least once and
+	at least twice. Rectangles only ever get removed with the exact same range they were added
+	with, which is why count never needs to be pushed down to the children.
+*/
+struct SegmentTree {
+	ys: Vec<u64>,
+	count: Vec<i32>,
+	once: Vec<u64>,
+	twice: Vec<u64>,
+}
+
+impl SegmentTree {
+	fn new(ys: Vec<u64>) -> Self {
+		let nodes = 4 * ys.len();
+		Self { ys, count: vec![0; nodes], once: vec![0; nodes], twice: vec![0; nodes] }
+	}
+
+	// how much of the whole y axis is covered at least once, node 1 is the root
+	fn covered_once(&self) -> u64 {
+		self.once[1]
+	}
+
+	fn covered_twice(&self) -> u64 {
+		self.twice[1]
+	}
+
+	fn add(&mut self, top: u64, bottom: u64, delta: i32) {
+		// the edges are always in ys so the searches can't fail
+		let from = self.ys.binary_search(&top).unwrap_or_else(|index| index);
+		let to = self.ys.binary_search(&bottom).unwrap_or_else(|index| index);
+		let leaves = self.ys.len() - 1;
+		self.update(1, 0, leaves, from, to, delta);
+	}
+
+	// node covers the leaves start..end, the update applies to from..to
+	fn update(&mut self, node: usize, start: usize, end: usize, from: usize, to: usize, delta: i32) {
+		if to <= start || end <= from {
+			return;
+		}
+		if from <= start && end <= to {
+			self.count[node] += delta;
+		} else {
+			let middle = (start + end) / 2;
+			self.update(2 * node, start, middle, from, to, delta);
+			self.update(2 * node + 1, middle, end, from, to, delta);
+		}
+
+		let full = self.ys[end] - self.ys[start];
+		let leaf = end - start == 1;
+		let (children_once, children_twice) = if leaf {
+			(0, 0)
+		} else {
+			(self.once[2 * node] + self.once[2 * node + 1], self.twice[2 * node] + self.twice[2 * node + 1])
+		};
+		(self.once[node], self.twice[node]) = match self.count[node] {
+			0 => (children_once, children_twice),
+			// covered once here, so anything covered once further down is covered twice overall
+			1 => (full, children_once),
+			_ => (full, full),
+		};
+	}
+}
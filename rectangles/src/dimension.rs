@@ -0,0 +1,103 @@
+/*
+	What a type needs to be usable as the width, height or position of a rectangle.
+	This is the "generic types and traits" reason for multiple impl blocks the methods chapter
+	hints at: Rectangle<T> gets its general methods for any T: Dimension and the algorithms
+	(packing, the quadtree, coverage) stay in their own impl blocks for plain u32 pixels.
+
+	Wide is a type big enough that adding two values or multiplying two of them can't overflow
+	(u32 widens to u64, i32 to i64 and so on), floats just stay as they are. The right and
+	bottom edges are handed back as Wide.
+	Area is what width times height comes out as. For plain numbers that's Wide again, but for a
+	Length from units.rs a length times a length is an area, not another length, so it gets its
+	own type there.
+*/
+use std::fmt::Debug;
+use std::ops::{Add, Sub};
+
+pub trait Dimension: Copy + PartialOrd + Debug {
+	type Wide: Copy + PartialOrd + Debug + Add<Output = Self::Wide> + Sub<Output = Self::Wide>;
+	type Area: Copy + PartialOrd + Debug;
+
+	fn widen(self) -> Self::Wide;
+
+	// only ever called with values that came from this type to begin with (like the distance
+	// between two edges of the same rectangle) so for the integers the cast can't cut anything off
+	fn narrow(wide: Self::Wide) -> Self;
+
+	fn zero() -> Self;
+
+	fn area(width: Self, height: Self) -> Self::Area;
+}
+
+// the integer impls only differ in their types so a macro writes them out
+macro_rules! integer_dimension {
+	($($narrow:ty => $wide:ty),*) => {
+		$(
+			impl Dimension for $narrow {
+				type Wide = $wide;
+				type Area = $wide;
+
+				fn widen(self) -> $wide {
+					<$wide>::from(self)
+				}
+
+				fn narrow(wide: $wide) -> Self {
+					wide as $narrow
+				}
+
+				fn zero() -> Self {
+					0
+				}
+
+				// done in the wide type so two u32 sides can never overflow the product (u32 gives back a u64)
+				fn area(width: Self, height: Self) -> $wide {
+					<$wide>::from(width) * <$wide>::from(height)
+				}
+			}
+		)*
+	};
+}
+
+integer_dimension!(u8 => u16, u16 => u32, u32 => u64, u64 => u128, i32 => i64, i64 => i128);
+
+impl Dimension for f32 {
+	type Wide = f32;
+	type Area = f32;
+
+	fn widen(self) -> f32 {
+		self
+	}
+
+	fn narrow(wide: f32) -> Self {
+		wide
+	}
+
+	fn zero() -> Self {
+		0.0
+	}
+
+	fn area(width: Self, height: Self) -> Self {
+		width * height
+	}
+}
+
+impl Dimension for f64 {
+	type Wide = f64;
+	type Area = f64;
+
+	fn widen(self) -> f64 {
+		self
+	}
+
+	fn narrow(wide: f64) -> Self {
+		wide
+	}
+
+	fn zero() -> Self {
+		0.0
+	}
+
+	fn area(width: Self, height: Self) -> Self {
+		width * height
+	}
+}
@@ -0,0 +1,153 @@
+/*
+	Finding the biggest free spot, for example to drop a new widget into a half full layout.
+	All three searches come down to the same classic problem, the largest rectangle in a histogram:
+		- largest_in_histogram: the problem itself, bars of width 1 standing next to each other.
+		- largest_empty_in_grid: go down the grid row by row and count for every column how many
+		  empty cells are stacked up ending at this row. Each row then is a histogram and the
+		  biggest rectangle over all of them is the answer.
+		- largest_empty_area: the edges of the obstacles (and of the bounds) cut the plane into a
+		  grid of uneven cells. The biggest empty rectangle always has its sides on those cuts, so
+		  it's the grid search again, only with columns and rows of different sizes.
+	The histogram itself is solved with a stack in one pass, O(n) per histogram.
+	When several rectangles share the biggest area, the one found first (the lowest bottom edge
+	while scanning top to bottom) is returned, so the answer is always the same for the same input.
+*/
+use crate::{PositionedRectangle, Rectangle};
+
+/*
+	The bars stand on a baseline at y = 0 and grow upwards, so the rectangle's x is the first bar
+	it covers and its y is always 0. None when every bar has a height of 0.
+*/
+pub fn largest_in_histogram(heights: &[u32]) -> Option<PositionedRectangle> {
+	let heights: Vec<u64> = heights.iter().map(|&height| u64::from(height)).collect();
+	let edges: Vec<u64> = (0..=heights.len() as u64).collect();
+	let best = best_in_histogram(&heights, &edges)?;
+	Some(PositionedRectangle::new(best.first as u32, 0, Rectangle::new(best.width as u32, best.height as u32)))
+}
+
+/*
+	grid[row][column] is true where the cell is taken. Rows shorter than the first one are treated
+	as taken past their end. The rectangle is measured in cells with row 0 at the top. None when
+	there isn't a single empty cell.
+*/
+pub fn largest_empty_in_grid(grid: &[Vec<bool>]) -> Option<PositionedRectangle> {
+	let columns = grid.first().map_or(0, |row| row.len());
+	let edges: Vec<u64> = (0..=columns as u64).collect();
+	let mut heights = vec![0u64; columns];
+	let mut best: Option<(Best, usize)> = None;
+	for (row, cells) in grid.iter().enumerate() {
+		for (column, height) in heights.iter_mut().enumerate() {
+			let taken = cells.get(column).copied().unwrap_or(true);
+			*height = if taken { 0 } else { *height + 1 };
+		}
+		if let Some(found) = best_in_histogram(&heights, &edges) {
+			if best.as_ref().is_none_or(|(current, _)| found.area > current.area) {
+				best = Some((found, row));
+			}
+		}
+	}
+	let (best, row) = best?;
+	let top = (row as u64 + 1 - best.height) as u32;
+	Some(PositionedRectangle::new(best.first as u32, top, Rectangle::new(best.width as u32, best.height as u32)))
+}
+
+/*
+	The biggest rectangle inside bounds that doesn't overlap any of the obstacles (touching is
+	fine). Obstacles sticking out of bounds only count for the part inside. bounds is cut off at
+	u32::MAX on the right and bottom so the answer can always be a PositionedRectangle.
+	None when the obstacles cover all of bounds.
+*/
+pub fn largest_empty_area(bounds: &PositionedRectangle, obstacles: &[PositionedRectangle]) -> Option<PositionedRectangle> {
+	let limit = u64::from(u32::MAX);
+	let bounds = PositionedRectangle {
+		width: (bounds.right().min(limit) - u64::from(bounds.x)) as u32,
+		height: (bounds.bottom().min(limit) - u64::from(bounds.y)) as u32,
+		..*bounds
+	};
+	let obstacles: Vec<PositionedRectangle> = obstacles.iter().filter_map(|obstacle| obstacle.intersection(&bounds)).collect();
+
+	// the cuts, every one of them is inside bounds and so fits in a u32
+	let mut xs: Vec<u64> = vec![u64::from(bounds.x), bounds.right()];
+	let mut ys: Vec<u64> = vec![u64::from(bounds.y), bounds.bottom()];
+	for obstacle in &obstacles {
+		xs.extend([u64::from(obstacle.x), obstacle.right()]);
+		ys.extend([u64::from(obstacle.y), obstacle.bottom()]);
+	}
+	for cuts in [&mut xs, &mut ys] {
+		cuts.sort_unstable();
+		cuts.dedup();
+	}
+	if xs.len() < 2 || ys.len() < 2 {
+		return None;
+	}
+
+	// taken[row][column] for the cell between cuts column..column + 1 and row..row + 1
+	let index_of = |cuts: &[u64], value: u64| cuts.binary_search(&value).unwrap_or_else(|index| index);
+	let mut taken = vec![vec![false; xs.len() - 1]; ys.len() - 1];
+	for obstacle in &obstacles {
+		let (first_column, end_column) = (index_of(&xs, u64::from(obstacle.x)), index_of(&xs, obstacle.right()));
+		let (first_row, end_row) = (index_of(&ys, u64::from(obstacle.y)), index_of(&ys, obstacle.bottom()));
+		for row in &mut taken[first_row..end_row] {
+			for cell in &mut row[first_column..end_column] {
+				*cell = true;
+			}
+		}
+	}
+
+	let mut heights = vec![0u64; xs.len() - 1];
+	let mut best: Option<(Best, usize)> = None;
+	for (row, cells) in taken.iter().enumerate() {
+		let row_height = ys[row + 1] - ys[row];
+		for (height, &cell_taken) in heights.iter_mut().zip(cells) {
+			*height = if cell_taken { 0 } else { *height + row_height };
+		}
+		if let Some(found) = best_in_histogram(&heights, &xs) {
+			if best.as_ref().is_none_or(|(current, _)| found.area > current.area) {
+				best = Some((found, row));
+			}
+		}
+	}
+	let (best, row) = best?;
+	Some(PositionedRectangle {
+		x: xs[best.first] as u32,
+		y: (ys[row + 1] - best.height) as u32,
+		width: best.width as u32,
+		height: best.height as u32,
+	})
+}
+
+struct Best {
+	area: u128,
+	// index of the first bar covered
+	first: usize,
+	width: u64,
+	height: u64,
+}
+
+/*
+	Bar i covers edges[i]..edges[i + 1] and is heights[i] tall. The stack holds bars in order of
+	increasing height; when a lower bar shows up, every taller bar on the stack can't reach any
+	further right, so the widest rectangle of that bar's height is measured right then.
+*/
+fn best_in_histogram(heights: &[u64], edges: &[u64]) -> Option<Best> {
+	let mut best: Option<Best> = None;
+	let mut stack: Vec<usize> = Vec::new();
+	for index in 0..=heights.len() {
+		// one extra bar of height 0 at the end empties the stack
+		let height = heights.get(index).copied().unwrap_or(0);
+		while let Some(&top) = stack.last() {
+			if heights[top] < height {
+				break;
+			}
+			stack.pop();
+			let first = stack.last().map_or(0, |&below| below + 1);
+			let width = edges[index] - edges[first];
+			let area = u128::from(heights[top]) * u128::from(width);
+			if area > 0 && best.as_ref().is_none_or(|current| area > current.area) {
+				best = Some(Best { area, first, width, height: heights[top] });
+			}
+		}
+		stack.push(index);
+	}
+	best
+}
@@ -0,0 +1,193 @@
+/*
+	The library half of the rectangles package. Cargo sees src/lib.rs next to src/main.rs and
+	builds two crates with the same name: this library and the binary that uses it.
+	The Rectangle here is the same struct the methods chapter ends up with (square, area, can_hold)
+	but made pub so the other modules and the binary can share one definition.
+
+	Both rectangle types are generic over the number type used for their sides (see dimension.rs).
+	The default is u32 so a plain `Rectangle` is still the square pixel one from the walkthrough, but
+	Rectangle<f64>, PositionedRectangle<i32> for signed coordinates or Rectangle<Length<Mm>>
+	from units.rs work too.
+*/
+pub mod coverage;
+pub mod dimension;
+pub mod free_space;
+pub mod packing;
+pub mod parse;
+pub mod physics;
+pub mod render;
+pub mod scaling;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod spatial;
+pub mod tiling;
+pub mod treemap;
+pub mod units;
+
+pub use dimension::Dimension;
+
+// a plain width x height with no position, measured in square pixels like the examples in examples/walkthrough.rs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+	feature = "serde",
+	serde(try_from = "schema::RawRectangle<T>", bound(deserialize = "T: Dimension + serde::Deserialize<'de>"))
+)]
+pub struct Rectangle<T = u32> {
+	pub width: T,
+	pub height: T,
+}
+
+impl<T: Dimension> Rectangle<T> {
+	pub fn new(width: T, height: T) -> Self {
+		Self { width, height }
+	}
+
+	pub fn square(size: T) -> Self {
+		Self { width: size, height: size }
+	}
+
+	// u32 sides give back a u64 so the product can't overflow, see Dimension::area
+	pub fn area(&self) -> T::Area {
+		T::area(self.width, self.height)
+	}
+
+	// strictly bigger on both sides, exactly like the methods chapter
+	pub fn can_hold(&self, other: &Rectangle<T>) -> bool {
+		self.width > other.width && self.height > other.height
+	}
+
+	// like can_hold but an equal side still counts, which is what placing things inside needs
+	pub fn can_fit(&self, other: &Rectangle<T>) -> bool {
+		self.width >= other.width && self.height >= other.height
+	}
+
+	// the same rectangle turned on its side
+	pub fn rotated(&self) -> Self {
+		Self { width: self.height, height: self.width }
+	}
+}
+
+impl Rectangle {
+	// width divided by height, infinite when the height is 0 (NaN if the width is 0 as well)
+	pub fn aspect_ratio(&self) -> f64 {
+		f64::from(self.width) / f64::from(self.height)
+	}
+}
+
+/*
+	A rectangle that also knows where it is. x and y are the top left corner, so the
+	rectangle covers x..x+width and y..y+height (the right and bottom edges are exclusive).
+	With a signed T the corner can be negative, the width and height are still expected to be
+	zero or more.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+	feature = "serde",
+	serde(try_from = "schema::RawPositionedRectangle<T>", bound(deserialize = "T: Dimension + serde::Deserialize<'de>"))
+)]
+pub struct PositionedRectangle<T = u32> {
+	pub x: T,
+	pub y: T,
+	pub width: T,
+	pub height: T,
+}
+
+impl<T: Dimension> PositionedRectangle<T> {
+	pub fn new(x: T, y: T, size: Rectangle<T>) -> Self {
+		Self { x, y, width: size.width, height: size.height }
+	}
+
+	// drops the position and hands back just the dimensions
+	pub fn size(&self) -> Rectangle<T> {
+		Rectangle::new(self.width, self.height)
+	}
+
+	pub fn area(&self) -> T::Area {
+		self.size().area()
+	}
+
+	// the edges are Wide because x + width can go past u32::MAX
+	pub fn right(&self) -> T::Wide {
+		self.x.widen() + self.width.widen()
+	}
+
+	pub fn bottom(&self) -> T::Wide {
+		self.y.widen() + self.height.widen()
+	}
+
+	// a width or height of zero (or less, for signed T) means there is no area at all
+	pub fn is_empty(&self) -> bool {
+		self.x.widen() >= self.right() || self.y.widen() >= self.bottom()
+	}
+
+	// the point is inside when it is on the left/top edge or anywhere up to (but not on) the right/bottom edge
+	pub fn contains_point(&self, x: T, y: T) -> bool {
+		self.x <= x && x.widen() < self.right() && self.y <= y && y.widen() < self.bottom()
+	}
+
+	// the positioned version of can_fit: other lies completely inside self, touching the edges is fine
+	pub fn contains(&self, other: &PositionedRectangle<T>) -> bool {
+		self.x <= other.x && self.y <= other.y && self.right() >= other.right() && self.bottom() >= other.bottom()
+	}
+
+	// true when the two share some area, rectangles that only touch along an edge don't count
+	// and neither does an empty (zero width or height) rectangle, it has no area to share
+	pub fn intersects(&self, other: &PositionedRectangle<T>) -> bool {
+		if self.is_empty() || other.is_empty() {
+			return false;
+		}
+		self.x.widen() < other.right()
+			&& other.x.widen() < self.right()
+			&& self.y.widen() < other.bottom()
+			&& other.y.widen() < self.bottom()
+	}
+
+	// the area the two have in common, None when they don't share any
+	pub fn intersection(&self, other: &PositionedRectangle<T>) -> Option<PositionedRectangle<T>> {
+		if !self.intersects(other) {
+			return None;
+		}
+		let x = larger(self.x, other.x);
+		let y = larger(self.y, other.y);
+		// both ends lie inside self and other, so the differences fit back into T
+		let width = T::narrow(smaller(self.right(), other.right()) - x.widen());
+		let height = T::narrow(smaller(self.bottom(), other.bottom()) - y.widen());
+		Some(PositionedRectangle { x, y, width, height })
+	}
+}
+
+impl PositionedRectangle {
+	// squared straight line distance from the point to the closest part of the rectangle, 0 when it is inside or on the edge
+	// u128 because the difference on each axis can reach u32::MAX and the squares are added together
+	pub fn distance_squared_to_point(&self, x: u32, y: u32) -> u128 {
+		let gap = |point: u32, start: u32, end: u64| -> u128 {
+			if point < start {
+				u128::from(start - point)
+			} else {
+				u128::from(u64::from(point).saturating_sub(end))
+			}
+		};
+		let dx = gap(x, self.x, self.right());
+		let dy = gap(y, self.y, self.bottom());
+		dx * dx + dy * dy
+	}
+}
+
+// Ord::max and min aren't there for floats, PartialOrd is enough for the way they're used here
+fn larger<T: PartialOrd>(a: T, b: T) -> T {
+	if a > b {
+		a
+	} else {
+		b
+	}
+}
+
+fn smaller<T: PartialOrd>(a: T, b: T) -> T {
+	if a < b {
+		a
+	} else {
+		b
+	}
+}
@@ -1,102 +1,256 @@
-// To make it explicit what the original two values are being used for
-// a struct makes it extremely clear and can be used as a parameter for 
-//the third area function; this way the function signature is more descriptive
-// this also means this can be used in other functions, like displaying
-// a rectangle to the screen 
-#[derive(Debug)] // this lets println! macro know to print debug info from the struct
-struct Rectangle {
-	width: u32,
-	height: u32,
+/*
+	rectangles <area|fits|sort|draw> [--by area|aspect] [--format table|json|svg] [FILE]
+
+	Reads rectangles from FILE (or stdin when there is no FILE or it is -), one per line as WxH
+	or W,H, and prints a report about them:
+		area  every rectangle's area and the total
+		fits  an N x N grid where row i, column j says whether rectangle i can_hold rectangle j
+		sort  the rectangles ordered by area or by aspect ratio (width / height), smallest first,
+		      by aspect the ones with a zero side come last
+		draw  the rectangles side by side in a row, labelled with their position, drawn by render.rs
+		      in box drawing characters (table) or as an SVG document (svg)
+	--format svg only goes with draw, and draw has no json.
+
+	The arguments are read by hand the same way the minigrep chapter does it, there are few enough
+	of them that a Config struct and a match is all it takes.
+	The chapter walkthrough that used to live here is in examples/walkthrough.rs.
+*/
+use std::io::Read;
+use std::{env, fs, io, process};
+
+use rectangles::parse::parse_rectangles;
+use rectangles::{render, PositionedRectangle, Rectangle};
+
+const USAGE: &str = "usage: rectangles <area|fits|sort|draw> [--by area|aspect] [--format table|json|svg] [FILE]";
+
+// how big draw lets the picture get in a terminal
+const DRAW_COLUMNS: usize = 80;
+const DRAW_ROWS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+	Area,
+	Fits,
+	Sort,
+	Draw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+	Area,
+	Aspect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+	Table,
+	Json,
+	Svg,
+}
+
+struct Config {
+	command: Command,
+	sort_by: SortKey,
+	format: Format,
+	// None means read stdin
+	path: Option<String>,
+}
+
+impl Config {
+	fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+		args.next(); // the program name
+
+		let command = match args.next().as_deref() {
+			Some("area") => Command::Area,
+			Some("fits") => Command::Fits,
+			Some("sort") => Command::Sort,
+			Some("draw") => Command::Draw,
+			Some(other) => return Err(format!("unknown command {other:?}")),
+			None => return Err(String::from("missing command")),
+		};
+
+		let mut config = Config { command, sort_by: SortKey::Area, format: Format::Table, path: None };
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"--by" => {
+					config.sort_by = match args.next().as_deref() {
+						Some("area") => SortKey::Area,
+						Some("aspect") => SortKey::Aspect,
+						other => return Err(format!("--by takes area or aspect, not {other:?}")),
+					}
+				}
+				"--format" => {
+					config.format = match args.next().as_deref() {
+						Some("table") => Format::Table,
+						Some("json") => Format::Json,
+						Some("svg") => Format::Svg,
+						other => return Err(format!("--format takes table, json or svg, not {other:?}")),
+					}
+				}
+				"-" => config.path = None,
+				flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+				_ if config.path.is_some() => return Err(String::from("only one input file can be given")),
+				path => config.path = Some(String::from(path)),
+			}
+		}
+		Ok(config)
+	}
 }
 
 fn main() {
-	let width1 = 30;
-	let height1 = 50;
-
-	println!(
-		"The area of the rectangle is {} square pixels.",
-		area(width1, height1)
-	);
-
-	let rect1 = (30, 50);
-	println!(
-		"The area of the rectangle is {} square pixels.",
-		area_with_dimensions(rect1)
-	);
-
-	let rect2 = Rectangle {
-		width: 30,
-		height: 50,
+	let config = Config::build(env::args()).unwrap_or_else(|err| {
+		eprintln!("{err}\n{USAGE}");
+		process::exit(2);
+	});
+
+	if let Err(err) = run(&config) {
+		eprintln!("rectangles: {err}");
+		process::exit(1);
+	}
+}
+
+fn run(config: &Config) -> Result<(), String> {
+	let input = match &config.path {
+		Some(path) => fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?,
+		None => {
+			let mut input = String::new();
+			io::stdin().read_to_string(&mut input).map_err(|err| format!("stdin: {err}"))?;
+			input
+		}
 	};
+	let rects = parse_rectangles(&input).map_err(|err| err.to_string())?;
 
-	println!(
-		"The area of the rectangle is {} square pixels.",
-		area_with_struct(&rect2)
-	);
-
-	/*
-		note that if we wanted to see the values of rectangle while debugging the program w can try to access this with:
-		println!("rect1 is {}", rect1);
-		however this throws an error 
-		   = help: the trait `std::fmt::Display` is not implemented for `Rectangle`
-		that we need to implement the `std::fmt::Display` trait.
-		the macro printLn! can do lots of formatting but it uses the above trait to do so
-		this display trait is essentially the output intended for the direct end user comsumption
-		the reason that this isn't defaulted is that some things may not NEED to be shown or are
-		private internals that the end user doesn't need to know about; so struct by default do not
-		have the display trait implemented
-
-		However there is a neat way to circumvent this and that is to tell the println! macro we wanna Debug a value in a struct
-		this section of the error explains: 
-			= note: in format strings you may be able to use `{:?}` (or {:#?} for pretty-print) instead
-		The caveat is that each struct needs to opt into this functionality which can be done by adding a outter attribute on the struct
-		#[derive(Debug)]
-	*/
-	// with the debug attribute opted into the struct we can now print the struct
-	// it may not be the nicest but it does print all the fields and their values for the entire instance
-	println!("rect2 is {:?}", rect2);
-	// there is even better syntax for larger structs to output a new line for each field
-	println!("rect2 is {:#?}", rect2);
-	/* 
-		there is another way to do this which is to use the debug macro `dbg!`
-		this takes ownership of an expression versus the println! macro which takes a reference
-		prints the file and line number where that dbg! happened, alongside the resultant value of that expression then
-		returns ownership of that value. 
-		this prints to the stderr versus println! which prints to stdout
-	*/
-	// example of dbg! macro in action
-	let scale = 2;
-	let rect3 = Rectangle {
-			width: dbg!(30 * scale), // this works because it takes ownership of the expression then returns the resultant value
-			height: 50,
+	let report = match (config.command, config.format) {
+		(Command::Area, Format::Table) => area_table(&rects),
+		(Command::Area, Format::Json) => area_json(&rects),
+		(Command::Fits, Format::Table) => fits_table(&rects),
+		(Command::Fits, Format::Json) => fits_json(&rects),
+		(Command::Sort, Format::Table) => sort_table(&sorted(&rects, config.sort_by)),
+		(Command::Sort, Format::Json) => sort_json(&sorted(&rects, config.sort_by)),
+		(Command::Draw, Format::Table) => render::ascii(&in_a_row(&rects)?, &[], DRAW_COLUMNS, DRAW_ROWS),
+		(Command::Draw, Format::Svg) => render::svg(&in_a_row(&rects)?, &[]),
+		(Command::Draw, Format::Json) => return Err(String::from("draw can't write json, use --format table or svg")),
+		(_, Format::Svg) => return Err(String::from("only draw can write svg")),
 	};
-	dbg!(&rect3); // if we don't want dbg! to take ownership we can pass a reference to it
-	// however because this area function is so specific to rectangles it makes sense to have it be a method of the struct
+	print!("{report}");
+	Ok(())
+}
 
+// left to right with their tops lined up, the way the input lists them
+fn in_a_row(rects: &[Rectangle]) -> Result<Vec<PositionedRectangle>, String> {
+	// None once the row has gone past u32::MAX, which is only a problem if there is another rectangle to place
+	let mut x = Some(0u32);
+	let mut row = Vec::new();
+	for rect in rects {
+		let left = x.ok_or("the rectangles are too wide to draw in a row")?;
+		row.push(PositionedRectangle::new(left, 0, *rect));
+		x = left.checked_add(rect.width);
+	}
+	Ok(row)
 }
 
-// this signature takes in two paramters but these are highly related and dependant on 
-// each other! Instead of having them unrelated we can have them be a tuple type struct
-fn area(width: u32, height: u32) -> u32 {
-	width * height
+fn area_table(rects: &[Rectangle]) -> String {
+	let mut out = format!("{:>5}  {:>12}  {:>20}\n", "#", "rectangle", "area");
+	for (index, rect) in rects.iter().enumerate() {
+		out += &format!("{:>5}  {:>12}  {:>20}\n", index + 1, rect.to_string(), rect.area());
+	}
+	out += &format!("{:>5}  {:>12}  {:>20}\n", "", "total", total_area(rects));
+	out
 }
-// this way the function signature is more descriptive and we can put the data into a tuple
-fn area_with_dimensions(dimensions: (u32, u32)) -> u32 {
-	// notice the syntax to access each of the dimensions in the tuple
-	// dot with index!
-	dimensions.0 * dimensions.1
-	// this is clearer than the previous function signature however it is still a little unclear
-	// there isn't any indication that the first value is the width and the second is the height
-	// instead we have to keep that in our mind which is which and because of that lack of
-	// explaination it can easily introduce errors!
+
+fn area_json(rects: &[Rectangle]) -> String {
+	let entries: Vec<String> = rects
+		.iter()
+		.map(|rect| format!("{{\"width\":{},\"height\":{},\"area\":{}}}", rect.width, rect.height, rect.area()))
+		.collect();
+	format!("{{\"rectangles\":[{}],\"total_area\":{}}}\n", entries.join(","), total_area(rects))
 }
 
-// this is the best way to do it because it is the most clear and descriptive it takes a reference to
-// a struct type rectangle
-// this also creates a immutable borrow of the struct and then ownership is maintained by the main function
-// this is nice so that main can continue to use the struct after the function is called
-fn area_with_struct(rectangle: &Rectangle) -> u32 {
-	// note that accessing the fiels of a struct do not take ownership!
-	rectangle.width * rectangle.height
+// every area fits in a u64, the sum of a lot of them might not
+fn total_area(rects: &[Rectangle]) -> u128 {
+	rects.iter().map(|rect| u128::from(rect.area())).sum()
 }
 
+fn fits_table(rects: &[Rectangle]) -> String {
+	// rows and columns are labelled by their 1 based position in the input
+	let mut out = String::from("can_hold");
+	for column in 1..=rects.len() {
+		out += &format!(" {column:>4}");
+	}
+	out.push('\n');
+	for (row, rect) in rects.iter().enumerate() {
+		out += &format!("{:>8}", row + 1);
+		for other in rects {
+			out += if rect.can_hold(other) { "  yes" } else { "    ." };
+		}
+		out.push('\n');
+	}
+	out
+}
+
+fn fits_json(rects: &[Rectangle]) -> String {
+	let rows: Vec<String> = rects
+		.iter()
+		.map(|rect| {
+			let row: Vec<String> = rects.iter().map(|other| rect.can_hold(other).to_string()).collect();
+			format!("[{}]", row.join(","))
+		})
+		.collect();
+	format!("{{\"rectangles\":[{}],\"can_hold\":[{}]}}\n", json_rectangles(rects), rows.join(","))
+}
+
+// pairs every rectangle with its 1 based position in the input so the sorted output can point back to it
+fn sorted(rects: &[Rectangle], key: SortKey) -> Vec<(usize, Rectangle)> {
+	let mut sorted: Vec<(usize, Rectangle)> = rects.iter().copied().enumerate().map(|(index, rect)| (index + 1, rect)).collect();
+	// both sorts are stable so rectangles that compare equal stay in input order
+	match key {
+		SortKey::Area => sorted.sort_by_key(|(_, rect)| rect.area()),
+		// a rectangle with a zero side has no shape to compare (0x0 is NaN), so those all go last in input order
+		SortKey::Aspect => sorted.sort_by(|(_, a), (_, b)| match (a.area() == 0, b.area() == 0) {
+			(false, false) => a.aspect_ratio().total_cmp(&b.aspect_ratio()),
+			(a_empty, b_empty) => a_empty.cmp(&b_empty),
+		}),
+	}
+	sorted
+}
+
+fn sort_table(sorted: &[(usize, Rectangle)]) -> String {
+	let mut out = format!("{:>5}  {:>12}  {:>20}  {:>10}\n", "#", "rectangle", "area", "aspect");
+	for (position, rect) in sorted {
+		let aspect = format!("{:.3}", rect.aspect_ratio());
+		out += &format!("{:>5}  {:>12}  {:>20}  {:>10}\n", position, rect.to_string(), rect.area(), aspect);
+	}
+	out
+}
+
+fn sort_json(sorted: &[(usize, Rectangle)]) -> String {
+	let entries: Vec<String> = sorted
+		.iter()
+		.map(|(position, rect)| {
+			format!(
+				"{{\"position\":{},\"width\":{},\"height\":{},\"area\":{},\"aspect_ratio\":{}}}",
+				position,
+				rect.width,
+				rect.height,
+				rect.area(),
+				json_number(rect.aspect_ratio())
+			)
+		})
+		.collect();
+	format!("{{\"rectangles\":[{}]}}\n", entries.join(","))
+}
+
+fn json_rectangles(rects: &[Rectangle]) -> String {
+	let entries: Vec<String> =
+		rects.iter().map(|rect| format!("{{\"width\":{},\"height\":{}}}", rect.width, rect.height)).collect();
+	entries.join(",")
+}
+
+// JSON has no infinity or NaN, a zero height gives null instead
+fn json_number(value: f64) -> String {
+	if value.is_finite() {
+		value.to_string()
+	} else {
+		String::from("null")
+	}
+}
@@ -0,0 +1,224 @@
+/*
+	Bin packing: can_hold answers "does this one rectangle fit inside", packing answers
+	"where do all of these go". Given a container and a list of items we hand back a position
+	for every item that made it in, the indexes of the ones that didn't and how much of the
+	container was left unused.
+	There are two heuristics:
+		- Shelf: rows (shelves) are filled left to right and a new shelf is opened underneath
+		  when an item doesn't fit on any existing one. Quick and simple but wastes the space
+		  above the shorter items on a shelf.
+		- MaxRects: keeps a list of every maximal free rectangle in the container and puts each
+		  item into the free rectangle it fits most snugly (best short side fit). Slower but
+		  packs noticeably tighter.
+*/
+use std::cmp::Reverse;
+
+use crate::{PositionedRectangle, Rectangle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heuristic {
+	Shelf,
+	MaxRects,
+}
+
+// where one item ended up; index points back into the slice that was passed to pack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+	pub index: usize,
+	pub position: PositionedRectangle,
+	// true when the item was turned on its side, position then holds the rotated dimensions
+	pub rotated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packing {
+	// sorted by index
+	pub placements: Vec<Placement>,
+	// indexes of the items that didn't fit, also sorted
+	pub unplaced: Vec<usize>,
+	// container area minus the area of everything that was placed
+	pub wasted_area: u64,
+}
+
+// a second impl block for Rectangle, this one only holds the packing method
+impl Rectangle {
+	pub fn pack(&self, items: &[Rectangle], heuristic: Heuristic, allow_rotation: bool) -> Packing {
+		let mut placements = match heuristic {
+			Heuristic::Shelf => pack_shelf(self, items, allow_rotation),
+			Heuristic::MaxRects => pack_max_rects(self, items, allow_rotation),
+		};
+		placements.sort_by_key(|placement| placement.index);
+
+		let mut placed = vec![false; items.len()];
+		for placement in &placements {
+			placed[placement.index] = true;
+		}
+		let unplaced = (0..items.len()).filter(|&index| !placed[index]).collect();
+
+		let used: u64 = placements.iter().map(|placement| placement.position.area()).sum();
+		Packing {
+			placements,
+			unplaced,
+			wasted_area: self.area() - used,
+		}
+	}
+}
+
+// the ways an item can be put down: as given, and turned on its side if that is allowed and makes a difference
+fn orientations(item: Rectangle, allow_rotation: bool) -> Vec<(Rectangle, bool)> {
+	let mut options = vec![(item, false)];
+	if allow_rotation && item.width != item.height {
+		options.push((item.rotated(), true));
+	}
+	options
+}
+
+// bigger items first, both heuristics do much better when the awkward pieces go in early
+fn placement_order(items: &[Rectangle], key: impl Fn(&Rectangle) -> u64) -> Vec<usize> {
+	let mut order: Vec<usize> = (0..items.len()).collect();
+	// the sort is stable so equal items keep their original order and the result stays deterministic
+	order.sort_by_key(|&index| Reverse(key(&items[index])));
+	order
+}
+
+struct Shelf {
+	y: u32,
+	height: u32,
+	used_width: u32,
+}
+
+fn pack_shelf(container: &Rectangle, items: &[Rectangle], allow_rotation: bool) -> Vec<Placement> {
+	let order = if allow_rotation {
+		placement_order(items, |item| u64::from(item.width.max(item.height)))
+	} else {
+		placement_order(items, |item| u64::from(item.height))
+	};
+
+	let mut shelves: Vec<Shelf> = Vec::new();
+	// top of the next shelf, u64 so stacking shelves can't overflow
+	let mut next_y: u64 = 0;
+	let mut placements = Vec::new();
+
+	for index in order {
+		let mut options = orientations(items[index], allow_rotation);
+
+		// on an existing shelf the taller orientation goes first since it uses up less of the row
+		options.sort_by_key(|(size, _)| Reverse(size.height));
+		let on_shelf = shelves.iter_mut().find_map(|shelf| {
+			options
+				.iter()
+				.find(|(size, _)| {
+					size.height <= shelf.height && u64::from(shelf.used_width) + u64::from(size.width) <= u64::from(container.width)
+				})
+				.map(|&(size, rotated)| {
+					let position = PositionedRectangle::new(shelf.used_width, shelf.y, size);
+					shelf.used_width += size.width;
+					(position, rotated)
+				})
+		});
+		if let Some((position, rotated)) = on_shelf {
+			placements.push(Placement { index, position, rotated });
+			continue;
+		}
+
+		// nothing had room so a new shelf is opened underneath, standing the item upright when it fits
+		// because the items are sorted by their longest side so later ones can still use the full shelf height
+		let remaining_height = u64::from(container.height) - next_y;
+		let new_shelf = options
+			.iter()
+			.find(|(size, _)| size.width <= container.width && u64::from(size.height) <= remaining_height);
+		if let Some(&(size, rotated)) = new_shelf {
+			// next_y <= container.height here so it fits back into a u32
+			let y = next_y as u32;
+			shelves.push(Shelf { y, height: size.height, used_width: size.width });
+			next_y += u64::from(size.height);
+			placements.push(Placement { index, position: PositionedRectangle::new(0, y, size), rotated });
+		}
+	}
+	placements
+}
+
+fn pack_max_rects(container: &Rectangle, items: &[Rectangle], allow_rotation: bool) -> Vec<Placement> {
+	let order = placement_order(items, |item| item.area());
+	let mut free = vec![PositionedRectangle::new(0, 0, *container)];
+	let mut placements = Vec::new();
+
+	for index in order {
+		// best short side fit: the free rectangle that leaves the smallest gap on its tighter side wins,
+		// the longer side's gap breaks ties
+		let mut best: Option<((u32, u32), PositionedRectangle, bool)> = None;
+		for (size, rotated) in orientations(items[index], allow_rotation) {
+			for space in &free {
+				if !space.size().can_fit(&size) {
+					continue;
+				}
+				let gap_x = space.width - size.width;
+				let gap_y = space.height - size.height;
+				let score = (gap_x.min(gap_y), gap_x.max(gap_y));
+				if best.is_none_or(|(best_score, _, _)| score < best_score) {
+					best = Some((score, PositionedRectangle::new(space.x, space.y, size), rotated));
+				}
+			}
+		}
+
+		if let Some((_, position, rotated)) = best {
+			split_free_space(&mut free, &position);
+			placements.push(Placement { index, position, rotated });
+		}
+	}
+	placements
+}
+
+/*
+	Every free rectangle the new item overlaps is cut into the (up to four) maximal pieces
+	left, right, above and below the item. Those pieces overlap each other on purpose, that is
+	what makes them maximal. Afterwards any free rectangle sitting completely inside another is
+	thrown away since it can never give a better fit than the one holding it.
+*/
+fn split_free_space(free: &mut Vec<PositionedRectangle>, used: &PositionedRectangle) {
+	let mut next = Vec::with_capacity(free.len() + 4);
+	for space in free.iter() {
+		if !space.intersects(used) {
+			next.push(*space);
+			continue;
+		}
+		// every subtraction below is guarded by the comparison in front of it
+		if used.x > space.x {
+			next.push(PositionedRectangle { width: used.x - space.x, ..*space });
+		}
+		if used.right() < space.right() {
+			next.push(PositionedRectangle {
+				x: used.right() as u32,
+				width: (space.right() - used.right()) as u32,
+				..*space
+			});
+		}
+		if used.y > space.y {
+			next.push(PositionedRectangle { height: used.y - space.y, ..*space });
+		}
+		if used.bottom() < space.bottom() {
+			next.push(PositionedRectangle {
+				y: used.bottom() as u32,
+				height: (space.bottom() - used.bottom()) as u32,
+				..*space
+			});
+		}
+	}
+
+	// remove anything enclosed by another free rectangle; of two identical ones only the first survives
+	let mut keep = vec![true; next.len()];
+	for i in 0..next.len() {
+		for j in 0..next.len() {
+			if i == j || !keep[j] || !next[j].contains(&next[i]) {
+				continue;
+			}
+			if next[i] != next[j] || j < i {
+				keep[i] = false;
+				break;
+			}
+		}
+	}
+	let mut keep = keep.into_iter();
+	next.retain(|_| keep.next().unwrap_or(false));
+	*free = next;
+}
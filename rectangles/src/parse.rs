@@ -0,0 +1,76 @@
+/*
+	Reading rectangles out of text. One rectangle per line, either as WxH (30x50, 30X50 or 30×50)
+	or as CSV (30,50). Blank lines and lines starting with # are skipped, and so is a first line
+	that looks like a CSV header (width,height). Spaces around the numbers don't matter.
+*/
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Rectangle;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+	// 1 based like an editor shows it, 0 when the text wasn't read from a file (FromStr)
+	pub line: usize,
+	pub message: String,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.line == 0 {
+			write!(f, "{}", self.message)
+		} else {
+			write!(f, "line {}: {}", self.line, self.message)
+		}
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for Rectangle {
+	type Err = ParseError;
+
+	fn from_str(text: &str) -> Result<Self, ParseError> {
+		let error = |message: String| ParseError { line: 0, message };
+		let text = text.trim();
+		let (width, height) = text
+			.split_once(['x', 'X', '×', ','])
+			.ok_or_else(|| error(format!("expected WxH or W,H but found {text:?}")))?;
+		let number = |side: &str, name: &str| {
+			side.trim()
+				.parse::<u32>()
+				.map_err(|err| error(format!("{name} {:?} is not a whole number of pixels: {err}", side.trim())))
+		};
+		Ok(Rectangle::new(number(width, "width")?, number(height, "height")?))
+	}
+}
+
+// prints the same WxH form that from_str reads
+impl fmt::Display for Rectangle {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}x{}", self.width, self.height)
+	}
+}
+
+pub fn parse_rectangles(input: &str) -> Result<Vec<Rectangle>, ParseError> {
+	let mut rects = Vec::new();
+	for (index, line) in input.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+		if rects.is_empty() && is_csv_header(line) {
+			continue;
+		}
+		let rect = line.parse().map_err(|err: ParseError| ParseError { line: index + 1, ..err })?;
+		rects.push(rect);
+	}
+	Ok(rects)
+}
+
+fn is_csv_header(line: &str) -> bool {
+	line.contains(',') && line.split(',').all(|field| {
+		let field = field.trim();
+		!field.is_empty() && field.chars().all(|c| c.is_alphabetic() || c == '_')
+	})
+}
@@ -0,0 +1,233 @@
+/*
+	Axis aligned (AABB) collisions and a small physics step for PositionedRectangle<f64>.
+	Moving a rectangle by its velocity and then checking for overlaps misses things: a fast
+	rectangle can jump clean over a thin wall between two steps (tunnelling). So step() sweeps
+	instead: it works out when during the step a moving rectangle would first touch something,
+	moves it only that far and lets it slide along whatever it hit for the rest of the step.
+	Any overlap still left after that (things that started out overlapping, or two moving
+	rectangles pushing into each other) is pushed apart along the shallower axis.
+
+	Like everywhere else the right and bottom edges are exclusive, so rectangles that only touch
+	are not colliding. The y axis points down, the same as screen coordinates.
+	Everything runs in a fixed order (by body index) so the same world always steps the same way.
+*/
+use crate::PositionedRectangle;
+
+// how many times one body may hit something and slide on within a single step
+const MAX_SLIDES: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vector {
+	pub x: f64,
+	pub y: f64,
+}
+
+impl Vector {
+	pub fn new(x: f64, y: f64) -> Self {
+		Self { x, y }
+	}
+
+	fn scaled(self, factor: f64) -> Self {
+		Vector::new(self.x * factor, self.y * factor)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Body {
+	pub rect: PositionedRectangle<f64>,
+	// units per second
+	pub velocity: Vector,
+	// static bodies (walls, floors) never move and aren't pushed around
+	pub fixed: bool,
+}
+
+impl Body {
+	pub fn moving(rect: PositionedRectangle<f64>, velocity: Vector) -> Self {
+		Self { rect, velocity, fixed: false }
+	}
+
+	pub fn fixed(rect: PositionedRectangle<f64>) -> Self {
+		Self { rect, velocity: Vector::default(), fixed: true }
+	}
+}
+
+// how two overlapping rectangles are stuck together
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+	// unit vector along one axis, the direction the first rectangle has to move to get out of the second
+	pub normal: Vector,
+	// how far it has to move that way
+	pub depth: f64,
+}
+
+// where a sweep runs into something
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+	// fraction of the motion (0 to 1) travelled before touching
+	pub time: f64,
+	// the side of the target that was hit, pointing back at the moving rectangle
+	pub normal: Vector,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collision {
+	pub body: usize,
+	pub other: usize,
+	pub normal: Vector,
+	// 0 for a swept hit, which stops at the surface; the overlap that was pushed out otherwise
+	pub depth: f64,
+	// fraction of the step that had gone by when it happened
+	pub time: f64,
+}
+
+/*
+	The contact between two rectangles that share some area, None if they don't. The normal is
+	along the axis where they overlap the least since that is the shortest way out. When both
+	axes overlap equally the y axis wins, which keeps something resting on a corner on top of it
+	instead of shoving it sideways.
+*/
+pub fn penetration(a: &PositionedRectangle<f64>, b: &PositionedRectangle<f64>) -> Option<Contact> {
+	if !a.intersects(b) {
+		return None;
+	}
+	let overlap_x = a.right().min(b.right()) - a.x.max(b.x);
+	let overlap_y = a.bottom().min(b.bottom()) - a.y.max(b.y);
+	// away from b's centre, comparing doubled centres saves the divisions
+	let away = |a_start: f64, a_end: f64, b_start: f64, b_end: f64| if a_start + a_end < b_start + b_end { -1.0 } else { 1.0 };
+	if overlap_x < overlap_y {
+		Some(Contact { normal: Vector::new(away(a.x, a.right(), b.x, b.right()), 0.0), depth: overlap_x })
+	} else {
+		Some(Contact { normal: Vector::new(0.0, away(a.y, a.bottom(), b.y, b.bottom())), depth: overlap_y })
+	}
+}
+
+/*
+	Moves `moving` by `motion` in a straight line and reports the first moment it would overlap
+	`target`. For each axis this works out the time the two start overlapping along that axis
+	(entry) and stop again (exit); they only really overlap while both axes do, so that's from the
+	later entry to the earlier exit.
+	Returns None when they never overlap during the motion, when they already overlap at the start
+	(that's penetration's job), and when they only touch or graze a corner. A hit exactly on a
+	corner counts as hitting the top or bottom side, for the same reason as in penetration.
+*/
+pub fn sweep(moving: &PositionedRectangle<f64>, motion: Vector, target: &PositionedRectangle<f64>) -> Option<Hit> {
+	let axis = |start: f64, end: f64, speed: f64, target_start: f64, target_end: f64| -> Option<(f64, f64)> {
+		if speed > 0.0 {
+			Some(((target_start - end) / speed, (target_end - start) / speed))
+		} else if speed < 0.0 {
+			Some(((target_end - start) / speed, (target_start - end) / speed))
+		} else if end <= target_start || start >= target_end {
+			// not moving on this axis and not lined up with the target on it, they can never meet
+			None
+		} else {
+			Some((f64::NEG_INFINITY, f64::INFINITY))
+		}
+	};
+	let (entry_x, exit_x) = axis(moving.x, moving.right(), motion.x, target.x, target.right())?;
+	let (entry_y, exit_y) = axis(moving.y, moving.bottom(), motion.y, target.y, target.bottom())?;
+
+	let entry = entry_x.max(entry_y);
+	let exit = exit_x.min(exit_y);
+	if entry >= exit || !(0.0..1.0).contains(&entry) {
+		return None;
+	}
+	let normal = if entry_x > entry_y {
+		Vector::new(-motion.x.signum(), 0.0)
+	} else {
+		Vector::new(0.0, -motion.y.signum())
+	};
+	Some(Hit { time: entry, normal })
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct World {
+	pub bodies: Vec<Body>,
+}
+
+impl World {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// the index handed back is what Collision::body and Collision::other refer to
+	pub fn add(&mut self, body: Body) -> usize {
+		self.bodies.push(body);
+		self.bodies.len() - 1
+	}
+
+	// advances every moving body by dt seconds and returns everything that touched along the way
+	pub fn step(&mut self, dt: f64) -> Vec<Collision> {
+		let mut collisions = Vec::new();
+		for index in 0..self.bodies.len() {
+			if !self.bodies[index].fixed {
+				self.sweep_body(index, dt, &mut collisions);
+			}
+		}
+		self.separate(&mut collisions);
+		collisions
+	}
+
+	fn sweep_body(&mut self, index: usize, dt: f64, collisions: &mut Vec<Collision>) {
+		let mut motion = self.bodies[index].velocity.scaled(dt);
+		// how much of the step this body still has to move through
+		let mut remaining = 1.0;
+		for _ in 0..MAX_SLIDES {
+			let rect = self.bodies[index].rect;
+			let first = (0..self.bodies.len())
+				.filter(|&other| other != index)
+				.filter_map(|other| sweep(&rect, motion, &self.bodies[other].rect).map(|hit| (other, hit)))
+				// strictly earlier only, so on a tie the lowest index is the one reported
+				.fold(None, |first: Option<(usize, Hit)>, (other, hit)| match first {
+					Some((_, earliest)) if earliest.time <= hit.time => first,
+					_ => Some((other, hit)),
+				});
+
+			let Some((other, hit)) = first else {
+				self.bodies[index].rect = moved(&rect, motion);
+				return;
+			};
+			self.bodies[index].rect = moved(&rect, motion.scaled(hit.time));
+			collisions.push(Collision { body: index, other, normal: hit.normal, depth: 0.0, time: 1.0 - remaining * (1.0 - hit.time) });
+
+			// stop dead along the normal and keep the rest of the motion to slide with
+			let body = &mut self.bodies[index];
+			motion = motion.scaled(1.0 - hit.time);
+			remaining *= 1.0 - hit.time;
+			if hit.normal.x != 0.0 {
+				motion.x = 0.0;
+				body.velocity.x = 0.0;
+			} else {
+				motion.y = 0.0;
+				body.velocity.y = 0.0;
+			}
+		}
+	}
+
+	// pushes apart whatever still overlaps, two moving bodies each move half the way
+	fn separate(&mut self, collisions: &mut Vec<Collision>) {
+		for a in 0..self.bodies.len() {
+			for b in a + 1..self.bodies.len() {
+				let (first, second) = (self.bodies[a], self.bodies[b]);
+				if first.fixed && second.fixed {
+					continue;
+				}
+				let Some(contact) = penetration(&first.rect, &second.rect) else {
+					continue;
+				};
+				let (share_a, share_b) = match (first.fixed, second.fixed) {
+					(false, false) => (0.5, 0.5),
+					(false, true) => (1.0, 0.0),
+					_ => (0.0, 1.0),
+				};
+				let push = contact.normal.scaled(contact.depth);
+				self.bodies[a].rect = moved(&first.rect, push.scaled(share_a));
+				self.bodies[b].rect = moved(&second.rect, push.scaled(-share_b));
+				collisions.push(Collision { body: a, other: b, normal: contact.normal, depth: contact.depth, time: 1.0 });
+			}
+		}
+	}
+}
+
+fn moved(rect: &PositionedRectangle<f64>, by: Vector) -> PositionedRectangle<f64> {
+	PositionedRectangle { x: rect.x + by.x, y: rect.y + by.y, ..*rect }
+}
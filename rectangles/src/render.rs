@@ -0,0 +1,169 @@
+/*
+	Drawing positioned rectangles so a layout can be looked at instead of read out of {:#?}.
+	Two outputs:
+		- svg: a standalone SVG document, every rectangle gets a colour and its label in the
+		  middle, and the spots where two or more overlap are painted over in red.
+		- ascii: box drawing characters scaled down to fit a terminal, with overlaps shaded ▒.
+	Both produce a String, and write_svg puts the SVG straight into a file.
+	labels[i] is printed on rects[i], rectangles without a label get their 1 based position.
+*/
+use std::path::Path;
+use std::{fs, io};
+
+use crate::PositionedRectangle;
+
+// fill colours handed out in turn, picked to stay readable with black text on top
+const PALETTE: [&str; 8] = ["#8ecae6", "#ffb703", "#90be6d", "#cdb4db", "#f4a261", "#a8dadc", "#e9c46a", "#bde0fe"];
+const OVERLAP_COLOUR: &str = "#e63946";
+
+fn label(labels: &[String], index: usize) -> String {
+	labels.get(index).cloned().unwrap_or_else(|| (index + 1).to_string())
+}
+
+// the smallest box around all of the rectangles as (left, top, right, bottom)
+fn bounds(rects: &[PositionedRectangle]) -> Option<(u64, u64, u64, u64)> {
+	let left = rects.iter().map(|rect| u64::from(rect.x)).min()?;
+	let top = rects.iter().map(|rect| u64::from(rect.y)).min()?;
+	let right = rects.iter().map(|rect| rect.right()).max()?;
+	let bottom = rects.iter().map(|rect| rect.bottom()).max()?;
+	Some((left, top, right, bottom))
+}
+
+// every pairwise overlap; fine for debugging sized inputs, coverage.rs is the tool for big ones
+fn overlaps(rects: &[PositionedRectangle]) -> Vec<PositionedRectangle> {
+	let mut found = Vec::new();
+	for (i, a) in rects.iter().enumerate() {
+		for b in &rects[i + 1..] {
+			found.extend(a.intersection(b));
+		}
+	}
+	found
+}
+
+// the characters that have a meaning in XML text and attributes
+fn escape_xml(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn svg(rects: &[PositionedRectangle], labels: &[String]) -> String {
+	let (left, top, right, bottom) = bounds(rects).unwrap_or((0, 0, 0, 0));
+	let (width, height) = (right - left, bottom - top);
+	let mut out = format!(
+		"<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{left} {top} {width} {height}\" width=\"{width}\" height=\"{height}\">\n"
+	);
+	for (index, rect) in rects.iter().enumerate() {
+		out += &format!(
+			"  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"black\" stroke-width=\"1\"/>\n",
+			rect.x,
+			rect.y,
+			rect.width,
+			rect.height,
+			PALETTE[index % PALETTE.len()]
+		);
+	}
+	for overlap in overlaps(rects) {
+		out += &format!(
+			"  <rect class=\"overlap\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{OVERLAP_COLOUR}\" fill-opacity=\"0.6\"/>\n",
+			overlap.x, overlap.y, overlap.width, overlap.height
+		);
+	}
+	// labels last so nothing gets drawn over them
+	for (index, rect) in rects.iter().enumerate() {
+		out += &format!(
+			"  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\" font-family=\"monospace\">{}</text>\n",
+			f64::from(rect.x) + f64::from(rect.width) / 2.0,
+			f64::from(rect.y) + f64::from(rect.height) / 2.0,
+			escape_xml(&label(labels, index))
+		);
+	}
+	out += "</svg>\n";
+	out
+}
+
+// svg into a file at path, replacing whatever was there
+pub fn write_svg(path: impl AsRef<Path>, rects: &[PositionedRectangle], labels: &[String]) -> io::Result<()> {
+	fs::write(path, svg(rects, labels))
+}
+
+/*
+	Every character cell stands for a block of the layout that is `scale` units wide and
+	2 * scale units tall, since a terminal character is about twice as tall as it is wide. scale
+	is the smallest whole number that squeezes the layout into max_columns and max_rows both, so a
+	1x4000000000 rectangle gets a handful of rows and not billions of them. A rectangle takes up
+	every cell it touches, so even a tiny one shows up as at least one cell.
+	A cell is shaded ▒ when part of an actual overlap falls in it. Two rectangles that only share
+	an edge can both touch the same cell once it's scaled down, but they don't overlap.
+*/
+pub fn ascii(rects: &[PositionedRectangle], labels: &[String], max_columns: usize, max_rows: usize) -> String {
+	let Some((left, top, right, bottom)) = bounds(rects) else {
+		return String::new();
+	};
+	let max_columns = max_columns.max(1) as u64;
+	let max_rows = max_rows.max(1) as u64;
+	let scale = (right - left).div_ceil(max_columns).max((bottom - top).div_ceil(2 * max_rows)).max(1);
+	let columns = (right - left).div_ceil(scale).max(1) as usize;
+	let rows = (bottom - top).div_ceil(2 * scale).max(1) as usize;
+
+	// (first column, last column, first row, last row) of the cells a rectangle touches
+	let cells_of = |rect: &PositionedRectangle| {
+		let first_column = ((u64::from(rect.x) - left) / scale) as usize;
+		let first_row = ((u64::from(rect.y) - top) / (2 * scale)) as usize;
+		let last_column = ((rect.right() - left).div_ceil(scale) as usize).saturating_sub(1).max(first_column);
+		let last_row = ((rect.bottom() - top).div_ceil(2 * scale) as usize).saturating_sub(1).max(first_row);
+		(first_column, last_column.min(columns - 1), first_row, last_row.min(rows - 1))
+	};
+	let cells: Vec<(usize, usize, usize, usize)> = rects.iter().map(cells_of).collect();
+
+	let mut grid = vec![vec![' '; columns]; rows];
+	for overlap in overlaps(rects) {
+		let (first_column, last_column, first_row, last_row) = cells_of(&overlap);
+		for row in &mut grid[first_row..=last_row] {
+			row[first_column..=last_column].fill('▒');
+		}
+	}
+
+	for (index, &(first_column, last_column, first_row, last_row)) in cells.iter().enumerate() {
+		for (row, line) in grid.iter_mut().enumerate().take(last_row + 1).skip(first_row) {
+			for (column, cell) in line.iter_mut().enumerate().take(last_column + 1).skip(first_column) {
+				let on_left = column == first_column;
+				let on_right = column == last_column;
+				let on_top = row == first_row;
+				let on_bottom = row == last_row;
+				let border = match (on_top, on_bottom, on_left, on_right) {
+					// a single cell rectangle
+					(true, true, true, true) => '□',
+					(true, true, _, _) => '─',
+					(_, _, true, true) => '│',
+					(true, _, true, _) => '┌',
+					(true, _, _, true) => '┐',
+					(_, true, true, _) => '└',
+					(_, true, _, true) => '┘',
+					(true, _, _, _) | (_, true, _, _) => '─',
+					(_, _, true, _) | (_, _, _, true) => '│',
+					_ => continue,
+				};
+				// where two borders cross draw a crossing instead of letting the later one win
+				*cell = match (*cell, border) {
+					(' ' | '▒', border) => border,
+					(existing, border) if existing == border => border,
+					_ => '┼',
+				};
+			}
+		}
+
+		// the label goes on the first row inside the border, cut short if the rectangle is too narrow
+		if last_row > first_row + 1 && last_column > first_column + 1 {
+			let room = last_column - first_column - 1;
+			for (offset, c) in label(labels, index).chars().take(room).enumerate() {
+				grid[first_row + 1][first_column + 1 + offset] = c;
+			}
+		}
+	}
+
+	let mut out = String::new();
+	for row in grid {
+		out.push_str(row.iter().collect::<String>().trim_end());
+		out.push('\n');
+	}
+	out
+}
@@ -0,0 +1,224 @@
+/*
+	Resizing rectangles, mostly for thumbnails: making a picture fit inside a box without
+	stretching it (scale_to_fit, the box may end up with empty bars) or covering the whole box
+	and cutting off what sticks out (scale_to_fill). Plus naming the common aspect ratios and a
+	small axis aligned affine transform for positioned rectangles.
+
+	Scaling happens in f64 and has to land back on whole pixels, a Rounding decides how.
+	Anything past u32::MAX ends up as u32::MAX, anything below 0 or NaN as 0.
+*/
+use crate::{PositionedRectangle, Rectangle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+	Nearest,
+	// floor, never bigger than the exact size
+	Down,
+	// ceil, never smaller than the exact size
+	Up,
+	// like Nearest but a side that wasn't 0 to begin with never rounds down to 0
+	NearestNonZero,
+}
+
+impl Rounding {
+	fn apply(self, exact: f64, original: u32) -> u32 {
+		// `as` saturates, which gives exactly the clamping described at the top
+		let rounded = match self {
+			Rounding::Nearest | Rounding::NearestNonZero => exact.round() as u32,
+			Rounding::Down => exact.floor() as u32,
+			Rounding::Up => exact.ceil() as u32,
+		};
+		if self == Rounding::NearestNonZero && original > 0 {
+			rounded.max(1)
+		} else {
+			rounded
+		}
+	}
+}
+
+// what scale_to_fill hands back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fill {
+	// the rectangle scaled up (or down) until it covers the container
+	pub scaled: Rectangle,
+	// the container sized part of `scaled` that is kept, centred
+	pub crop: PositionedRectangle,
+	// the same part in the original, unscaled rectangle's coordinates, i.e. which pixels of the source to use
+	pub source_crop: PositionedRectangle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+	Landscape,
+	Portrait,
+	Square,
+}
+
+/*
+	The well known ratios, named long side first whichever way round the rectangle is; pair
+	with orientation() to know which. Anything not within 1% of one of these is Other.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AspectRatio {
+	Square,
+	// 4:3, old monitors and TVs, most phone cameras
+	FourThree,
+	// 3:2, 35mm film and many DSLRs
+	ThreeTwo,
+	// 16:10, laptop screens
+	SixteenTen,
+	// 1.618:1
+	Golden,
+	// 16:9, HD video
+	SixteenNine,
+	// 21:9 ultrawide (really 64:27 on most monitors, both land here)
+	TwentyOneNine,
+	// long side divided by short side
+	Other(f64),
+}
+
+const NAMED_RATIOS: [(f64, AspectRatio); 7] = [
+	(1.0, AspectRatio::Square),
+	(4.0 / 3.0, AspectRatio::FourThree),
+	(1.5, AspectRatio::ThreeTwo),
+	(1.6, AspectRatio::SixteenTen),
+	(1.618_034, AspectRatio::Golden),
+	(16.0 / 9.0, AspectRatio::SixteenNine),
+	(2.35, AspectRatio::TwentyOneNine),
+];
+const RATIO_TOLERANCE: f64 = 0.01;
+
+impl Rectangle {
+	pub fn scale(&self, factor: f64, rounding: Rounding) -> Rectangle {
+		Rectangle::new(
+			rounding.apply(f64::from(self.width) * factor, self.width),
+			rounding.apply(f64::from(self.height) * factor, self.height),
+		)
+	}
+
+	/*
+		The biggest copy of self with the same aspect ratio that still fits inside container
+		("contain" in CSS). Sides are clamped to the container so Rounding::Up can't poke out.
+		A rectangle with a zero side keeps it at zero and scales the other side to the container.
+	*/
+	pub fn scale_to_fit(&self, container: &Rectangle, rounding: Rounding) -> Rectangle {
+		let factor = match (self.width, self.height) {
+			(0, 0) => return Rectangle::square(0),
+			(0, height) => f64::from(container.height) / f64::from(height),
+			(width, 0) => f64::from(container.width) / f64::from(width),
+			(width, height) => {
+				(f64::from(container.width) / f64::from(width)).min(f64::from(container.height) / f64::from(height))
+			}
+		};
+		let scaled = self.scale(factor, rounding);
+		Rectangle::new(scaled.width.min(container.width), scaled.height.min(container.height))
+	}
+
+	/*
+		The smallest copy of self with the same aspect ratio that covers all of container ("cover"
+		in CSS), with the container sized crop taken from its centre. Sides are clamped so they are
+		never smaller than the container, whatever the rounding does. A rectangle with a zero side
+		can't cover anything, it comes back as is with empty crops.
+	*/
+	pub fn scale_to_fill(&self, container: &Rectangle, rounding: Rounding) -> Fill {
+		if self.width == 0 || self.height == 0 {
+			let empty = PositionedRectangle::new(0, 0, Rectangle::square(0));
+			return Fill { scaled: *self, crop: empty, source_crop: empty };
+		}
+		let factor = (f64::from(container.width) / f64::from(self.width)).max(f64::from(container.height) / f64::from(self.height));
+		let scaled = self.scale(factor, rounding);
+		let scaled = Rectangle::new(scaled.width.max(container.width), scaled.height.max(container.height));
+
+		let crop = PositionedRectangle::new((scaled.width - container.width) / 2, (scaled.height - container.height) / 2, *container);
+		// back through the exact factor, rounded inwards so the source crop never reads past the source
+		let source_crop = if factor > 0.0 {
+			let left = (f64::from(crop.x) / factor).ceil().min(f64::from(self.width));
+			let top = (f64::from(crop.y) / factor).ceil().min(f64::from(self.height));
+			let right = (crop.right() as f64 / factor).floor().clamp(left, f64::from(self.width));
+			let bottom = (crop.bottom() as f64 / factor).floor().clamp(top, f64::from(self.height));
+			PositionedRectangle {
+				x: left as u32,
+				y: top as u32,
+				width: (right - left) as u32,
+				height: (bottom - top) as u32,
+			}
+		} else {
+			PositionedRectangle::new(0, 0, Rectangle::square(0))
+		};
+		Fill { scaled, crop, source_crop }
+	}
+
+	pub fn orientation(&self) -> Orientation {
+		match self.width.cmp(&self.height) {
+			std::cmp::Ordering::Greater => Orientation::Landscape,
+			std::cmp::Ordering::Less => Orientation::Portrait,
+			std::cmp::Ordering::Equal => Orientation::Square,
+		}
+	}
+
+	// closest named ratio within the tolerance; a zero side gives Other(infinity) (or NaN for 0x0)
+	pub fn aspect_class(&self) -> AspectRatio {
+		let long = f64::from(self.width.max(self.height));
+		let short = f64::from(self.width.min(self.height));
+		let ratio = long / short;
+		NAMED_RATIOS
+			.iter()
+			.map(|&(named, class)| ((ratio / named - 1.0).abs(), class))
+			.filter(|&(difference, _)| difference <= RATIO_TOLERANCE)
+			.min_by(|a, b| a.0.total_cmp(&b.0))
+			.map_or(AspectRatio::Other(ratio), |(_, class)| class)
+	}
+}
+
+/*
+	x' = scale_x * x + translate_x and the same for y. Without rotation or shearing a rectangle
+	stays an axis aligned rectangle, which is the only kind this crate knows. A negative scale
+	mirrors; the result is flipped back round so width and height stay positive.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine {
+	pub scale_x: f64,
+	pub scale_y: f64,
+	pub translate_x: f64,
+	pub translate_y: f64,
+}
+
+impl Default for Affine {
+	fn default() -> Self {
+		Affine::identity()
+	}
+}
+
+impl Affine {
+	pub fn identity() -> Self {
+		Affine { scale_x: 1.0, scale_y: 1.0, translate_x: 0.0, translate_y: 0.0 }
+	}
+
+	pub fn scale(scale_x: f64, scale_y: f64) -> Self {
+		Affine { scale_x, scale_y, ..Affine::identity() }
+	}
+
+	pub fn translate(translate_x: f64, translate_y: f64) -> Self {
+		Affine { translate_x, translate_y, ..Affine::identity() }
+	}
+
+	// self first and then next, as one transform
+	pub fn then(&self, next: &Affine) -> Affine {
+		Affine {
+			scale_x: self.scale_x * next.scale_x,
+			scale_y: self.scale_y * next.scale_y,
+			translate_x: self.translate_x * next.scale_x + next.translate_x,
+			translate_y: self.translate_y * next.scale_y + next.translate_y,
+		}
+	}
+
+	pub fn apply_point(&self, x: f64, y: f64) -> (f64, f64) {
+		(self.scale_x * x + self.translate_x, self.scale_y * y + self.translate_y)
+	}
+
+	pub fn apply(&self, rect: &PositionedRectangle<f64>) -> PositionedRectangle<f64> {
+		let (x1, y1) = self.apply_point(rect.x, rect.y);
+		let (x2, y2) = self.apply_point(rect.right(), rect.bottom());
+		PositionedRectangle { x: x1.min(x2), y: y1.min(y2), width: (x2 - x1).abs(), height: (y2 - y1).abs() }
+	}
+}
@@ -0,0 +1,142 @@
+/*
+	Saving and loading layouts, only built with the serde feature:
+		cargo build --features serde
+	Rectangle and PositionedRectangle serialize as plain {width, height} / {x, y, width, height}
+	maps so any serde format works (the tests use JSON and TOML). Loading goes through the Raw
+	versions below first and only turns into the real thing once the sides check out, so a file
+	with a zero or negative width is an error instead of a rectangle that breaks things later.
+	Saving a Layout runs the same check, so one with an empty rectangle in it (Rectangle::square(0),
+	or what treemap gives a zero weight) fails to save instead of making a file that won't load.
+
+	Layout is the document to put in a file. It carries a version number so the format can change
+	later; only CURRENT_VERSION is accepted right now.
+*/
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde::ser::Error as _;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::{Dimension, PositionedRectangle, Rectangle};
+
+pub const CURRENT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaError {
+	// the side ("width" or "height") that was 0 or less (or NaN for floats)
+	NotPositive(&'static str),
+	UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SchemaError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			SchemaError::NotPositive(side) => write!(f, "{side} has to be greater than 0"),
+			SchemaError::UnsupportedVersion(version) => {
+				write!(f, "layout version {version} is not supported, only version {CURRENT_VERSION} is")
+			}
+		}
+	}
+}
+
+impl std::error::Error for SchemaError {}
+
+// partial_cmp so that a NaN (which isn't greater, less or equal to anything) fails too
+fn check_sides<T: Dimension>(width: T, height: T) -> Result<(), SchemaError> {
+	if width.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+		return Err(SchemaError::NotPositive("width"));
+	}
+	if height.partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+		return Err(SchemaError::NotPositive("height"));
+	}
+	Ok(())
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RawRectangle<T> {
+	width: T,
+	height: T,
+}
+
+impl<T: Dimension> TryFrom<RawRectangle<T>> for Rectangle<T> {
+	type Error = SchemaError;
+
+	fn try_from(raw: RawRectangle<T>) -> Result<Self, SchemaError> {
+		check_sides(raw.width, raw.height)?;
+		Ok(Rectangle::new(raw.width, raw.height))
+	}
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RawPositionedRectangle<T> {
+	x: T,
+	y: T,
+	width: T,
+	height: T,
+}
+
+// only the size is checked, a negative x or y is fine for the signed types
+impl<T: Dimension> TryFrom<RawPositionedRectangle<T>> for PositionedRectangle<T> {
+	type Error = SchemaError;
+
+	fn try_from(raw: RawPositionedRectangle<T>) -> Result<Self, SchemaError> {
+		check_sides(raw.width, raw.height)?;
+		Ok(PositionedRectangle { x: raw.x, y: raw.y, width: raw.width, height: raw.height })
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "RawLayout<T>", bound(deserialize = "T: Dimension + Deserialize<'de>"))]
+pub struct Layout<T: Dimension = u32> {
+	pub version: u32,
+	#[serde(default)]
+	pub rectangles: Vec<Rectangle<T>>,
+	#[serde(default)]
+	pub positioned: Vec<PositionedRectangle<T>>,
+}
+
+impl<T: Dimension> Layout<T> {
+	pub fn new(rectangles: Vec<Rectangle<T>>, positioned: Vec<PositionedRectangle<T>>) -> Self {
+		Layout { version: CURRENT_VERSION, rectangles, positioned }
+	}
+}
+
+// what a Layout saves as once it has been checked, borrowed so saving doesn't copy the lists
+#[derive(Serialize)]
+#[serde(rename = "Layout", bound(serialize = "T: Dimension + Serialize"))]
+struct CheckedLayout<'a, T: Dimension> {
+	version: u32,
+	rectangles: &'a [Rectangle<T>],
+	positioned: &'a [PositionedRectangle<T>],
+}
+
+impl<T: Dimension + Serialize> Serialize for Layout<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let sizes = self.rectangles.iter().copied().chain(self.positioned.iter().map(|rect| rect.size()));
+		for rect in sizes {
+			check_sides(rect.width, rect.height).map_err(S::Error::custom)?;
+		}
+		CheckedLayout { version: self.version, rectangles: &self.rectangles, positioned: &self.positioned }.serialize(serializer)
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "T: Dimension + Deserialize<'de>"))]
+pub(crate) struct RawLayout<T: Dimension> {
+	version: u32,
+	#[serde(default)]
+	rectangles: Vec<Rectangle<T>>,
+	#[serde(default)]
+	positioned: Vec<PositionedRectangle<T>>,
+}
+
+impl<T: Dimension> TryFrom<RawLayout<T>> for Layout<T> {
+	type Error = SchemaError;
+
+	fn try_from(raw: RawLayout<T>) -> Result<Self, SchemaError> {
+		if raw.version != CURRENT_VERSION {
+			return Err(SchemaError::UnsupportedVersion(raw.version));
+		}
+		Ok(Layout { version: raw.version, rectangles: raw.rectangles, positioned: raw.positioned })
+	}
+}
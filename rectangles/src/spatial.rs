@@ -0,0 +1,183 @@
+/*
+	A quadtree over positioned rectangles so "what is under this point" and "what overlaps this
+	region" don't have to look at every rectangle.
+	The tree covers a fixed region that is cut into four quarters, each quarter into four more
+	and so on. A rectangle is stored in the smallest quarter that holds it completely, so a query
+	only has to walk down the quarters that touch it. Anything that sticks out of the region
+	the tree was made for simply stays at the top, it is still found, just without the speed up.
+	Rectangles get an id from insert which is what the queries hand back and what remove takes.
+*/
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::PositionedRectangle;
+
+// how many rectangles a quarter holds before it gets split into four smaller ones
+const NODE_CAPACITY: usize = 8;
+// stops rectangles that all sit on the same spot from splitting forever
+const MAX_DEPTH: usize = 16;
+
+struct Node {
+	bounds: PositionedRectangle,
+	depth: usize,
+	items: Vec<usize>,
+	// indexes into QuadTree::nodes, the nodes live in one Vec instead of pointing at each other
+	children: Option<[usize; 4]>,
+}
+
+pub struct QuadTree {
+	nodes: Vec<Node>,
+	// indexed by id: the rectangle and the node it lives in, None once it has been removed
+	entries: Vec<Option<(PositionedRectangle, usize)>>,
+	len: usize,
+}
+
+impl QuadTree {
+	pub fn new(bounds: PositionedRectangle) -> Self {
+		Self {
+			nodes: vec![Node { bounds, depth: 0, items: Vec::new(), children: None }],
+			entries: Vec::new(),
+			len: 0,
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	pub fn get(&self, id: usize) -> Option<PositionedRectangle> {
+		self.entries.get(id).copied().flatten().map(|(rect, _)| rect)
+	}
+
+	// returns the id the rectangle can be looked up and removed by later
+	pub fn insert(&mut self, rect: PositionedRectangle) -> usize {
+		let id = self.entries.len();
+		self.entries.push(None);
+		self.place(id, rect, 0);
+		self.len += 1;
+		id
+	}
+
+	pub fn remove(&mut self, id: usize) -> Option<PositionedRectangle> {
+		let (rect, node) = self.entries.get_mut(id)?.take()?;
+		self.nodes[node].items.retain(|&item| item != id);
+		self.len -= 1;
+		Some(rect)
+	}
+
+	// ids of every rectangle that contains the point, smallest id first
+	pub fn query_point(&self, x: u32, y: u32) -> Vec<usize> {
+		let mut found = Vec::new();
+		let mut stack = vec![0];
+		while let Some(node) = stack.pop() {
+			let node = &self.nodes[node];
+			found.extend(node.items.iter().copied().filter(|&id| self.rect(id).contains_point(x, y)));
+			if let Some(children) = node.children {
+				stack.extend(children.into_iter().filter(|&child| self.nodes[child].bounds.contains_point(x, y)));
+			}
+		}
+		found.sort_unstable();
+		found
+	}
+
+	// ids of every rectangle that shares some area with the region, smallest id first
+	pub fn query_range(&self, region: &PositionedRectangle) -> Vec<usize> {
+		let mut found = Vec::new();
+		let mut stack = vec![0];
+		while let Some(node) = stack.pop() {
+			let node = &self.nodes[node];
+			found.extend(node.items.iter().copied().filter(|&id| self.rect(id).intersects(region)));
+			if let Some(children) = node.children {
+				stack.extend(children.into_iter().filter(|&child| self.nodes[child].bounds.intersects(region)));
+			}
+		}
+		found.sort_unstable();
+		found
+	}
+
+	/*
+		The id of the rectangle closest to the point (distance 0 when the point is inside it).
+		Nodes and rectangles go into one priority queue ordered by their distance to the point, so the
+		first rectangle that comes out is the closest and nodes further away than it are never opened.
+		When several are equally close the smallest id wins.
+	*/
+	pub fn nearest(&self, x: u32, y: u32) -> Option<usize> {
+		// (distance, 0 for a node and 1 for a rectangle, index): nodes at a distance are opened before
+		// any rectangle at that same distance is handed back, which keeps the smallest id rule true
+		let mut queue = BinaryHeap::new();
+		queue.push(Reverse((0, 0, 0)));
+		while let Some(Reverse((_, kind, index))) = queue.pop() {
+			if kind == 1 {
+				return Some(index);
+			}
+			let node = &self.nodes[index];
+			for &id in &node.items {
+				queue.push(Reverse((self.rect(id).distance_squared_to_point(x, y), 1, id)));
+			}
+			if let Some(children) = node.children {
+				for child in children {
+					queue.push(Reverse((self.nodes[child].bounds.distance_squared_to_point(x, y), 0, child)));
+				}
+			}
+		}
+		None
+	}
+
+	fn rect(&self, id: usize) -> PositionedRectangle {
+		// ids in a node are always live, remove takes them out of the node along with the entry
+		self.entries[id].expect("live id").0
+	}
+
+	// walks down from node to the smallest quarter that holds rect completely
+	fn place(&mut self, id: usize, rect: PositionedRectangle, mut node: usize) {
+		while let Some(children) = self.nodes[node].children {
+			match children.into_iter().find(|&child| self.nodes[child].bounds.contains(&rect)) {
+				Some(child) => node = child,
+				None => break,
+			}
+		}
+		self.nodes[node].items.push(id);
+		self.entries[id] = Some((rect, node));
+
+		let full = &self.nodes[node];
+		if full.children.is_none() && full.items.len() > NODE_CAPACITY && full.depth < MAX_DEPTH {
+			self.split(node);
+		}
+	}
+
+	fn split(&mut self, node: usize) {
+		let bounds = self.nodes[node].bounds;
+		if bounds.width < 2 || bounds.height < 2 {
+			return;
+		}
+		let half_width = bounds.width / 2;
+		let half_height = bounds.height / 2;
+		// saturating so a region reaching the very edge of u32 can't overflow, such a quarter just ends up empty
+		let middle_x = bounds.x.saturating_add(half_width);
+		let middle_y = bounds.y.saturating_add(half_height);
+		let quarters = [
+			PositionedRectangle { x: bounds.x, y: bounds.y, width: half_width, height: half_height },
+			PositionedRectangle { x: middle_x, y: bounds.y, width: bounds.width - half_width, height: half_height },
+			PositionedRectangle { x: bounds.x, y: middle_y, width: half_width, height: bounds.height - half_height },
+			PositionedRectangle { x: middle_x, y: middle_y, width: bounds.width - half_width, height: bounds.height - half_height },
+		];
+
+		let depth = self.nodes[node].depth + 1;
+		let first = self.nodes.len();
+		for bounds in quarters {
+			self.nodes.push(Node { bounds, depth, items: Vec::new(), children: None });
+		}
+		self.nodes[node].children = Some([first, first + 1, first + 2, first + 3]);
+
+		// everything is placed again from this node, the ones that straddle a quarter line stay where they are
+		let items = std::mem::take(&mut self.nodes[node].items);
+		for id in items {
+			let rect = self.rect(id);
+			self.place(id, rect, node);
+		}
+	}
+}
@@ -0,0 +1,227 @@
+/*
+	Cutting a canvas into tiles, e.g. to hand each tile to its own render thread.
+		- grid: N columns by M rows of (almost) equal tiles. When the size doesn't divide evenly the
+		  first columns/rows get one pixel more, so tiles never differ by more than a pixel.
+		- tiles: tiles of a fixed size, a Remainder policy decides what happens to the strip left
+		  over on the right and bottom when the canvas isn't a whole number of tiles.
+		- guillotine: one straight cut all the way across, giving two pieces. On a positioned
+		  rectangle near the far end of u32 the second piece can start past u32::MAX, which a
+		  position can't hold, so that's an error.
+	grid and tiles both hand back a Tiles iterator that can walk the tiles row by row or in Z order
+	(Morton order), which keeps neighbouring tiles close together in the sequence and so tends to
+	be kinder to caches. Z order is worked out as the tiles are asked for, a grid with billions of
+	tiles doesn't need a list of them all first. The tiles never overlap and always add up to
+	exactly the canvas area (with Remainder::Pad, once they're clipped back to the canvas).
+*/
+use std::fmt;
+
+use crate::{PositionedRectangle, Rectangle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Remainder {
+	// the last column/row of tiles is cut down to what is left
+	Clip,
+	// every tile keeps the full size and the last ones hang over the edge of the canvas
+	Pad,
+	// what is left is added to the last column/row, which makes those tiles bigger
+	Merge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileOrder {
+	RowMajor,
+	ZOrder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cut {
+	// a vertical line this far from the left edge, giving a left and a right piece
+	Vertical(u32),
+	// a horizontal line this far from the top edge, giving a top and a bottom piece
+	Horizontal(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CutError {
+	// where the second piece would have started
+	PastU32Max(u64),
+}
+
+impl fmt::Display for CutError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CutError::PastU32Max(at) => write!(f, "the second piece would start at {at}, past u32::MAX"),
+		}
+	}
+}
+
+impl std::error::Error for CutError {}
+
+// how one axis is divided up: the start and length of every piece along it
+#[derive(Debug, Clone, Copy)]
+enum Spans {
+	Even { total: u32, count: u32 },
+	Fixed { total: u32, tile: u32, remainder: Remainder },
+}
+
+impl Spans {
+	fn count(&self) -> u32 {
+		match *self {
+			Spans::Even { total: _, count } => count,
+			Spans::Fixed { total: 0, .. } | Spans::Fixed { tile: 0, .. } => 0,
+			Spans::Fixed { total, tile, remainder: Remainder::Merge } => (total / tile).max(1),
+			Spans::Fixed { total, tile, .. } => total.div_ceil(tile),
+		}
+	}
+
+	fn span(&self, index: u32) -> (u32, u32) {
+		match *self {
+			Spans::Even { total, count } => {
+				let (base, extra) = (total / count, total % count);
+				(index * base + index.min(extra), base + u32::from(index < extra))
+			}
+			Spans::Fixed { total, tile, remainder } => {
+				let start = index * tile;
+				let length = match remainder {
+					Remainder::Clip => tile.min(total - start),
+					Remainder::Pad => tile,
+					Remainder::Merge if index + 1 == self.count() => total - start,
+					Remainder::Merge => tile,
+				};
+				(start, length)
+			}
+		}
+	}
+}
+
+pub struct Tiles {
+	columns: Spans,
+	rows: Spans,
+	/*
+		Z order only: the square blocks of (column, row, side) still to walk, the next one on top.
+		The Z curve visits a block's four quarters top left, top right, bottom left, bottom right,
+		each of them the same way, so a block is swapped for its quarters until it's a single tile.
+		Blocks past the last column or row are dropped. It never holds more than 3 blocks for each
+		halving on the way down, however many tiles there are.
+	*/
+	blocks: Vec<(u64, u64, u64)>,
+	order: TileOrder,
+	next: u64,
+}
+
+impl Tiles {
+	fn new(columns: Spans, rows: Spans, order: TileOrder) -> Self {
+		let mut blocks = Vec::new();
+		if order == TileOrder::ZOrder {
+			let side = u64::from(columns.count().max(rows.count())).next_power_of_two();
+			blocks.push((0, 0, side));
+		}
+		Tiles { columns, rows, blocks, order, next: 0 }
+	}
+
+	// the next tile along the Z curve
+	fn next_in_z_order(&mut self) -> Option<(u32, u32)> {
+		let (columns, rows) = (u64::from(self.columns()), u64::from(self.rows()));
+		while let Some((column, row, side)) = self.blocks.pop() {
+			if column >= columns || row >= rows {
+				continue;
+			}
+			if side == 1 {
+				return Some((column as u32, row as u32));
+			}
+			let half = side / 2;
+			// pushed backwards so the top left quarter comes off first
+			self.blocks.extend([(column + half, row + half, half), (column, row + half, half), (column + half, row, half), (column, row, half)]);
+		}
+		None
+	}
+
+	pub fn columns(&self) -> u32 {
+		self.columns.count()
+	}
+
+	pub fn rows(&self) -> u32 {
+		self.rows.count()
+	}
+
+	fn len(&self) -> u64 {
+		u64::from(self.columns()) * u64::from(self.rows())
+	}
+}
+
+impl Iterator for Tiles {
+	type Item = PositionedRectangle;
+
+	fn next(&mut self) -> Option<PositionedRectangle> {
+		if self.next >= self.len() {
+			return None;
+		}
+		let (column, row) = match self.order {
+			TileOrder::RowMajor => ((self.next % u64::from(self.columns())) as u32, (self.next / u64::from(self.columns())) as u32),
+			TileOrder::ZOrder => self.next_in_z_order()?,
+		};
+		self.next += 1;
+		let (x, width) = self.columns.span(column);
+		let (y, height) = self.rows.span(row);
+		Some(PositionedRectangle { x, y, width, height })
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let left = usize::try_from(self.len() - self.next).unwrap_or(usize::MAX);
+		(left, Some(left))
+	}
+}
+
+impl Rectangle {
+	/*
+		columns x rows tiles covering the whole rectangle, positioned from its top left corner.
+		A column or row count of 0 gives no tiles, and so does asking for more columns than there
+		are pixels across (or rows than pixels down) since some tiles would have to be empty.
+	*/
+	pub fn grid(&self, columns: u32, rows: u32, order: TileOrder) -> Tiles {
+		let (columns, rows) = if columns > self.width || rows > self.height { (0, 0) } else { (columns, rows) };
+		Tiles::new(Spans::Even { total: self.width, count: columns }, Spans::Even { total: self.height, count: rows }, order)
+	}
+
+	// tiles of the given size, positioned from the top left corner; a zero sized tile gives no tiles
+	pub fn tiles(&self, tile: Rectangle, remainder: Remainder, order: TileOrder) -> Tiles {
+		Tiles::new(
+			Spans::Fixed { total: self.width, tile: tile.width, remainder },
+			Spans::Fixed { total: self.height, tile: tile.height, remainder },
+			order,
+		)
+	}
+
+	// a guillotine cut of the whole rectangle, see PositionedRectangle::guillotine
+	pub fn guillotine(&self, cut: Cut) -> (PositionedRectangle, PositionedRectangle) {
+		PositionedRectangle::new(0, 0, *self).guillotine(cut).expect("starting from 0 the second piece starts at most at the width, a u32")
+	}
+}
+
+impl PositionedRectangle {
+	/*
+		The two pieces either side of the cut, an offset past the far edge is moved back onto it
+		(leaving an empty second piece). The error is for when the second piece would start past
+		u32::MAX, which can only happen when the rectangle itself reaches past it.
+	*/
+	pub fn guillotine(&self, cut: Cut) -> Result<(PositionedRectangle, PositionedRectangle), CutError> {
+		let start = |position: u32, offset: u32| {
+			let at = u64::from(position) + u64::from(offset);
+			u32::try_from(at).map_err(|_| CutError::PastU32Max(at))
+		};
+		match cut {
+			Cut::Vertical(offset) => {
+				let offset = offset.min(self.width);
+				let left = PositionedRectangle { width: offset, ..*self };
+				let right = PositionedRectangle { x: start(self.x, offset)?, width: self.width - offset, ..*self };
+				Ok((left, right))
+			}
+			Cut::Horizontal(offset) => {
+				let offset = offset.min(self.height);
+				let top = PositionedRectangle { height: offset, ..*self };
+				let bottom = PositionedRectangle { y: start(self.y, offset)?, height: self.height - offset, ..*self };
+				Ok((top, bottom))
+			}
+		}
+	}
+}
@@ -0,0 +1,105 @@
+/*
+	Squarified treemap (Bruls, Huizing and van Wijk): splits a rectangle into one piece per item
+	with each piece's area proportional to the item's weight, while keeping the pieces as close
+	to squares as it can so they stay easy to see and compare.
+	Items are handled biggest first. They're added one at a time to a row along the shorter side
+	of the space that is still free, and as soon as adding the next item would make the row's
+	worst aspect ratio worse, the row is fixed in place and a new one starts in what is left.
+	The same weights always give the same layout; ties keep their input order.
+*/
+use std::cmp::Ordering;
+
+use crate::{PositionedRectangle, Rectangle};
+
+impl Rectangle {
+	/*
+		One rectangle per weight, in the same order as the weights. The layout is done in f64 so
+		the areas come out exactly proportional; treemap_pixels rounds it onto whole pixels.
+		Weights that are zero, negative or NaN get an empty rectangle in the top left corner.
+	*/
+	pub fn treemap(&self, weights: &[f64]) -> Vec<PositionedRectangle<f64>> {
+		let empty = PositionedRectangle::new(0.0, 0.0, Rectangle::square(0.0));
+		let mut layout = vec![empty; weights.len()];
+
+		let mut order: Vec<usize> = (0..weights.len()).filter(|&index| weights[index] > 0.0).collect();
+		// biggest first, sort_by is stable so equal weights keep their input order
+		order.sort_by(|&a, &b| weights[b].partial_cmp(&weights[a]).unwrap_or(Ordering::Equal));
+		let total: f64 = order.iter().map(|&index| weights[index]).sum();
+		let bounds_area = self.area() as f64;
+		if total <= 0.0 || bounds_area == 0.0 || !total.is_finite() {
+			return layout;
+		}
+
+		// each item's share of the bounding area, in the order they get laid out
+		let areas: Vec<(usize, f64)> = order.iter().map(|&index| (index, weights[index] / total * bounds_area)).collect();
+		let mut free = PositionedRectangle::new(0.0, 0.0, Rectangle::new(f64::from(self.width), f64::from(self.height)));
+		let mut row: Vec<(usize, f64)> = Vec::new();
+		for &item in &areas {
+			let side = free.width.min(free.height);
+			let mut with_item = row.clone();
+			with_item.push(item);
+			if row.is_empty() || worst_ratio(&with_item, side) <= worst_ratio(&row, side) {
+				row = with_item;
+			} else {
+				free = place_row(&row, free, &mut layout);
+				row = vec![item];
+			}
+		}
+		if !row.is_empty() {
+			place_row(&row, free, &mut layout);
+		}
+		layout
+	}
+
+	/*
+		The same layout with every edge rounded to the nearest whole pixel. Neighbours round their
+		shared edge the same way so there are no gaps or overlaps, the price is that the areas are
+		only proportional to within a pixel along each edge.
+	*/
+	pub fn treemap_pixels(&self, weights: &[f64]) -> Vec<PositionedRectangle> {
+		self.treemap(weights)
+			.iter()
+			.map(|rect| {
+				let (left, top) = (rect.x.round() as u32, rect.y.round() as u32);
+				let (right, bottom) = (rect.right().round() as u32, rect.bottom().round() as u32);
+				PositionedRectangle { x: left, y: top, width: right - left, height: bottom - top }
+			})
+			.collect()
+	}
+}
+
+// the most stretched (furthest from 1:1) item if the row were laid along a side this long
+fn worst_ratio(row: &[(usize, f64)], side: f64) -> f64 {
+	let sum: f64 = row.iter().map(|(_, area)| area).sum();
+	let largest = row.iter().map(|&(_, area)| area).fold(f64::MIN, f64::max);
+	let smallest = row.iter().map(|&(_, area)| area).fold(f64::MAX, f64::min);
+	let side_squared = side * side;
+	let sum_squared = sum * sum;
+	(side_squared * largest / sum_squared).max(sum_squared / (side_squared * smallest))
+}
+
+// lays the row along the shorter side of free and hands back the space left over next to it
+fn place_row(row: &[(usize, f64)], free: PositionedRectangle<f64>, layout: &mut [PositionedRectangle<f64>]) -> PositionedRectangle<f64> {
+	let sum: f64 = row.iter().map(|(_, area)| area).sum();
+	if free.width >= free.height {
+		// a column down the left edge
+		let column_width = sum / free.height;
+		let mut y = free.y;
+		for &(index, area) in row {
+			let height = area / column_width;
+			layout[index] = PositionedRectangle::new(free.x, y, Rectangle::new(column_width, height));
+			y += height;
+		}
+		PositionedRectangle { x: free.x + column_width, width: (free.width - column_width).max(0.0), ..free }
+	} else {
+		// a row along the top edge
+		let row_height = sum / free.width;
+		let mut x = free.x;
+		for &(index, area) in row {
+			let width = area / row_height;
+			layout[index] = PositionedRectangle::new(x, free.y, Rectangle::new(width, row_height));
+			x += width;
+		}
+		PositionedRectangle { y: free.y + row_height, height: (free.height - row_height).max(0.0), ..free }
+	}
+}
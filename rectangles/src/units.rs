@@ -0,0 +1,243 @@
+/*
+	Lengths that carry their unit in the type. A Length<Mm> and a Length<Px> are different types,
+	so adding them, comparing them or putting one where the other is expected doesn't compile;
+	one of them has to be converted with `to` first. The units themselves are empty structs
+	(unit-like structs from the structs chapter) that only exist to be that type parameter.
+
+	Pixels only mean something physical at a given resolution, so every conversion takes a Dpi.
+	Millimetres and inches don't care about it but take it anyway so every conversion looks the same.
+
+	Length implements Dimension so a Rectangle<Length<Mm>> gets new, can_hold, area and friends
+	like any other rectangle. Its area comes back as an Area<Mm>, square millimetres, which is a
+	type of its own so an area can't be added to (or mixed up with) a length either.
+*/
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Sub};
+
+use crate::{Dimension, PositionedRectangle, Rectangle};
+
+pub trait Unit: Copy + PartialEq + PartialOrd + std::fmt::Debug {
+	// the short name printed after a value
+	const SUFFIX: &'static str;
+
+	// how many of this unit make one inch
+	fn per_inch(dpi: Dpi) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Px;
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Mm;
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct In;
+
+impl Unit for Px {
+	const SUFFIX: &'static str = "px";
+
+	fn per_inch(dpi: Dpi) -> f64 {
+		dpi.0
+	}
+}
+
+impl Unit for Mm {
+	const SUFFIX: &'static str = "mm";
+
+	fn per_inch(_dpi: Dpi) -> f64 {
+		25.4
+	}
+}
+
+impl Unit for In {
+	const SUFFIX: &'static str = "in";
+
+	fn per_inch(_dpi: Dpi) -> f64 {
+		1.0
+	}
+}
+
+// dots (pixels) per inch, 96 is what browsers and most desktops assume
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Dpi(pub f64);
+
+impl Default for Dpi {
+	fn default() -> Self {
+		Dpi(96.0)
+	}
+}
+
+/// Mixing units doesn't compile, one side has to be converted with `to` first:
+/// ```compile_fail
+/// use rectangles::units::{inches, mm};
+/// let _ = mm(10.0) + inches(1.0);
+/// ```
+/// and neither does treating an area as a length:
+/// ```compile_fail
+/// use rectangles::units::mm;
+/// use rectangles::Rectangle;
+/// let _ = Rectangle::new(mm(2.0), mm(3.0)).area() + mm(3.0);
+/// ```
+/// while the same things done properly do:
+/// ```
+/// use rectangles::units::{inches, mm, Dpi, Mm};
+/// use rectangles::Rectangle;
+/// let _ = mm(10.0) + inches(1.0).to::<Mm>(Dpi::default());
+/// let _ = Rectangle::new(mm(2.0), mm(3.0)).area() + mm(1.0) * mm(3.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Length<U: Unit> {
+	pub value: f64,
+	unit: PhantomData<U>,
+}
+
+impl<U: Unit> Length<U> {
+	pub fn new(value: f64) -> Self {
+		Self { value, unit: PhantomData }
+	}
+
+	pub fn to<V: Unit>(self, dpi: Dpi) -> Length<V> {
+		Length::new(self.value / U::per_inch(dpi) * V::per_inch(dpi))
+	}
+}
+
+impl<U: Unit> std::fmt::Display for Length<U> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{}{}", self.value, U::SUFFIX)
+	}
+}
+
+// shorthands so an A4 page can be written as Rectangle::new(mm(210.0), mm(297.0))
+pub fn px(value: f64) -> Length<Px> {
+	Length::new(value)
+}
+
+pub fn mm(value: f64) -> Length<Mm> {
+	Length::new(value)
+}
+
+pub fn inches(value: f64) -> Length<In> {
+	Length::new(value)
+}
+
+// only the same unit on both sides, this is what turns mixing units into a compile error
+impl<U: Unit> Add for Length<U> {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		Length::new(self.value + other.value)
+	}
+}
+
+impl<U: Unit> Sub for Length<U> {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		Length::new(self.value - other.value)
+	}
+}
+
+// scaling by a plain number keeps the unit
+impl<U: Unit> Mul<f64> for Length<U> {
+	type Output = Self;
+
+	fn mul(self, factor: f64) -> Self {
+		Length::new(self.value * factor)
+	}
+}
+
+// a length times a length is an area in square units of U
+impl<U: Unit> Mul for Length<U> {
+	type Output = Area<U>;
+
+	fn mul(self, other: Self) -> Area<U> {
+		Area::new(self.value * other.value)
+	}
+}
+
+// square units of U, what the area of a rectangle measured in U comes back as
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Area<U: Unit> {
+	pub value: f64,
+	unit: PhantomData<U>,
+}
+
+impl<U: Unit> Area<U> {
+	pub fn new(value: f64) -> Self {
+		Self { value, unit: PhantomData }
+	}
+
+	// both sides get converted, so the factor is the one for a length squared
+	pub fn to<V: Unit>(self, dpi: Dpi) -> Area<V> {
+		let factor = V::per_inch(dpi) / U::per_inch(dpi);
+		Area::new(self.value * factor * factor)
+	}
+}
+
+impl<U: Unit> std::fmt::Display for Area<U> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{}{}²", self.value, U::SUFFIX)
+	}
+}
+
+impl<U: Unit> Add for Area<U> {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		Area::new(self.value + other.value)
+	}
+}
+
+impl<U: Unit> Sub for Area<U> {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		Area::new(self.value - other.value)
+	}
+}
+
+impl<U: Unit> Mul<f64> for Area<U> {
+	type Output = Self;
+
+	fn mul(self, factor: f64) -> Self {
+		Area::new(self.value * factor)
+	}
+}
+
+impl<U: Unit> Dimension for Length<U> {
+	type Wide = Self;
+	type Area = Area<U>;
+
+	fn widen(self) -> Self {
+		self
+	}
+
+	fn narrow(wide: Self) -> Self {
+		wide
+	}
+
+	fn zero() -> Self {
+		Length::new(0.0)
+	}
+
+	fn area(width: Self, height: Self) -> Area<U> {
+		width * height
+	}
+}
+
+impl<U: Unit> Rectangle<Length<U>> {
+	pub fn to<V: Unit>(&self, dpi: Dpi) -> Rectangle<Length<V>> {
+		Rectangle::new(self.width.to(dpi), self.height.to(dpi))
+	}
+}
+
+impl<U: Unit> PositionedRectangle<Length<U>> {
+	pub fn to<V: Unit>(&self, dpi: Dpi) -> PositionedRectangle<Length<V>> {
+		PositionedRectangle {
+			x: self.x.to(dpi),
+			y: self.y.to(dpi),
+			width: self.width.to(dpi),
+			height: self.height.to(dpi),
+		}
+	}
+}
@@ -0,0 +1,172 @@
+/*
+	Runs the rectangles binary itself, with the input on stdin, and checks what it prints and the
+	exit code: 0 when it worked, 1 when the input or the command couldn't be done, 2 for bad arguments.
+*/
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+// exit code, stdout and stderr
+fn run(args: &[&str], input: &str) -> (i32, String, String) {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_rectangles"))
+		.args(args)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.unwrap();
+	child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+	let output = child.wait_with_output().unwrap();
+	(output.status.code().unwrap(), String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+// stdout of a run that has to work
+fn report(args: &[&str], input: &str) -> String {
+	let (code, stdout, stderr) = run(args, input);
+	assert_eq!(code, 0, "{args:?}: {stderr}");
+	stdout
+}
+
+#[test]
+fn bad_arguments_print_the_usage() {
+	for (args, message) in [
+		(&[][..], "missing command"),
+		(&["volume"][..], "unknown command \"volume\""),
+		(&["sort", "--by"][..], "--by takes area or aspect, not None"),
+		(&["sort", "--by", "size"][..], "--by takes area or aspect, not Some(\"size\")"),
+		(&["area", "--format", "xml"][..], "--format takes table, json or svg, not Some(\"xml\")"),
+		(&["area", "--verbose"][..], "unknown option --verbose"),
+		(&["area", "a.txt", "b.txt"][..], "only one input file can be given"),
+	] {
+		let (code, stdout, stderr) = run(args, "");
+		assert_eq!(code, 2, "{args:?}");
+		assert!(stdout.is_empty());
+		assert_eq!(stderr.lines().collect::<Vec<_>>(), [message, "usage: rectangles <area|fits|sort|draw> [--by area|aspect] [--format table|json|svg] [FILE]"]);
+	}
+}
+
+#[test]
+fn area() {
+	let input = "# a comment\nwidth,height\n3,4\n\n5x6\n";
+	assert_eq!(
+		report(&["area"], input),
+		"    #     rectangle                  area
+    1           3x4                    12
+    2           5x6                    30
+              total                    42
+"
+	);
+	assert_eq!(
+		report(&["area", "--format", "json"], input),
+		"{\"rectangles\":[{\"width\":3,\"height\":4,\"area\":12},{\"width\":5,\"height\":6,\"area\":30}],\"total_area\":42}\n"
+	);
+}
+
+#[test]
+fn the_total_area_doesnt_overflow() {
+	let input = "4294967295x4294967295\n".repeat(3);
+	let json = report(&["area", "--format", "json"], &input);
+	assert!(json.ends_with(&format!("\"total_area\":{}}}\n", 3 * u128::from(u64::MAX - 2 * u64::from(u32::MAX)))), "{json}");
+}
+
+#[test]
+fn fits() {
+	let input = "3x4\n5x6\n2x2\n";
+	assert_eq!(
+		report(&["fits"], input),
+		"\
+can_hold    1    2    3
+       1    .    .  yes
+       2  yes    .  yes
+       3    .    .    .
+"
+	);
+	assert_eq!(
+		report(&["fits", "--format", "json"], input),
+		"{\"rectangles\":[{\"width\":3,\"height\":4},{\"width\":5,\"height\":6},{\"width\":2,\"height\":2}],\"can_hold\":[[false,false,true],[true,false,true],[false,false,false]]}\n"
+	);
+}
+
+#[test]
+fn sort_by_area_keeps_ties_in_input_order() {
+	assert_eq!(
+		report(&["sort"], "5x6\n2x2\n4x1\n"),
+		"    #     rectangle                  area      aspect
+    2           2x2                     4       1.000
+    3           4x1                     4       4.000
+    1           5x6                    30       0.833
+"
+	);
+}
+
+#[test]
+fn sort_by_aspect_puts_empty_rectangles_last() {
+	let input = "0x0\n2x1\n4x0\n1x2\n0x3\n";
+	assert_eq!(
+		report(&["sort", "--by", "aspect"], input),
+		"    #     rectangle                  area      aspect
+    4           1x2                     2       0.500
+    2           2x1                     2       2.000
+    1           0x0                     0         NaN
+    3           4x0                     0         inf
+    5           0x3                     0       0.000
+"
+	);
+	// JSON has no NaN or infinity, those come out as null
+	assert_eq!(
+		report(&["sort", "--by", "aspect", "--format", "json"], input),
+		"{\"rectangles\":[\
+{\"position\":4,\"width\":1,\"height\":2,\"area\":2,\"aspect_ratio\":0.5},\
+{\"position\":2,\"width\":2,\"height\":1,\"area\":2,\"aspect_ratio\":2},\
+{\"position\":1,\"width\":0,\"height\":0,\"area\":0,\"aspect_ratio\":null},\
+{\"position\":3,\"width\":4,\"height\":0,\"area\":0,\"aspect_ratio\":null},\
+{\"position\":5,\"width\":0,\"height\":3,\"area\":0,\"aspect_ratio\":0}]}\n"
+	);
+}
+
+#[test]
+fn draw() {
+	assert_eq!(
+		report(&["draw"], "10x6\n10x6\n"),
+		"\
+┌────────┐┌────────┐
+│1       ││2       │
+└────────┘└────────┘
+"
+	);
+	let svg = report(&["draw", "--format", "svg"], "3x2\n2x4\n");
+	assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 5 4\""), "{svg}");
+	assert!(svg.contains("<rect x=\"3\" y=\"0\" width=\"2\" height=\"4\""), "{svg}");
+}
+
+#[test]
+fn formats_a_command_cant_write() {
+	assert_eq!(run(&["draw", "--format", "json"], "3x2\n"), (1, String::new(), String::from("rectangles: draw can't write json, use --format table or svg\n")));
+	assert_eq!(run(&["fits", "--format", "svg"], "3x2\n"), (1, String::new(), String::from("rectangles: only draw can write svg\n")));
+	// the second one starts at u32::MAX, which is as far as a row goes, so there's nowhere to put the third
+	let (code, _, stderr) = run(&["draw"], "4294967295x1\n1x1\n1x1\n");
+	assert_eq!((code, stderr.as_str()), (1, "rectangles: the rectangles are too wide to draw in a row\n"));
+}
+
+#[test]
+fn bad_input_gives_the_line() {
+	assert_eq!(run(&["area"], "3x4\n\n5y6\n"), (1, String::new(), String::from("rectangles: line 3: expected WxH or W,H but found \"5y6\"\n")));
+	let (code, _, stderr) = run(&["area"], "3x-4\n");
+	assert_eq!(code, 1);
+	assert!(stderr.starts_with("rectangles: line 1: height \"-4\" is not a whole number of pixels"), "{stderr}");
+}
+
+#[test]
+fn reading_a_file() {
+	let path = env::temp_dir().join(format!("rectangles-cli-{}.txt", std::process::id()));
+	fs::write(&path, "3x4\n").unwrap();
+	let path_text = path.to_str().unwrap();
+	// the file wins over stdin, and - goes back to stdin
+	assert_eq!(report(&["area", "--format", "json", path_text], "5x6\n"), "{\"rectangles\":[{\"width\":3,\"height\":4,\"area\":12}],\"total_area\":12}\n");
+	assert_eq!(report(&["area", "--format", "json", path_text, "-"], "5x6\n"), "{\"rectangles\":[{\"width\":5,\"height\":6,\"area\":30}],\"total_area\":30}\n");
+	fs::remove_file(&path).unwrap();
+
+	let (code, _, stderr) = run(&["area", path_text], "");
+	assert_eq!(code, 1);
+	assert!(stderr.starts_with(&format!("rectangles: {path_text}: ")), "{stderr}");
+}
@@ -0,0 +1,86 @@
+// the sweep line checked against counting every unit square on a small grid, plus a few by hand
+use proptest::prelude::*;
+use rectangles::coverage::{measure, overlap_area, uncovered_area, union_area, Coverage};
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn rect(x: u32, y: u32, width: u32, height: u32) -> PositionedRectangle {
+	PositionedRectangle::new(x, y, Rectangle::new(width, height))
+}
+
+fn positioned() -> impl Strategy<Value = PositionedRectangle> {
+	(0..30u32, 0..30u32, 0..15u32, 0..15u32).prop_map(|(x, y, width, height)| rect(x, y, width, height))
+}
+
+// how many of the unit squares in 0..45 each way are covered once or more, and twice or more
+fn count_squares(rects: &[PositionedRectangle], bounds: Option<&PositionedRectangle>) -> (u128, u128) {
+	let (mut once, mut twice) = (0, 0);
+	for y in 0..45 {
+		for x in 0..45 {
+			if bounds.is_some_and(|bounds| !bounds.contains_point(x, y)) {
+				continue;
+			}
+			match rects.iter().filter(|rect| rect.contains_point(x, y)).count() {
+				0 => {}
+				1 => once += 1,
+				_ => (once, twice) = (once + 1, twice + 1),
+			}
+		}
+	}
+	(once, twice)
+}
+
+proptest! {
+	#[test]
+	fn measure_matches_counting_squares(rects in prop::collection::vec(positioned(), 0..10)) {
+		let (once, twice) = count_squares(&rects, None);
+		let total = rects.iter().map(|rect| u128::from(rect.area())).sum();
+		prop_assert_eq!(measure(&rects), Coverage { total_area: total, union_area: once, overlap_area: twice });
+	}
+
+	#[test]
+	fn uncovered_matches_counting_squares(bounds in positioned(), rects in prop::collection::vec(positioned(), 0..10)) {
+		let (once, _) = count_squares(&rects, Some(&bounds));
+		prop_assert_eq!(uncovered_area(&bounds, &rects), u128::from(bounds.area()) - once);
+	}
+}
+
+#[test]
+fn nothing_covers_nothing() {
+	assert_eq!(measure(&[]), Coverage::default());
+	assert_eq!(measure(&[rect(3, 3, 0, 5)]), Coverage::default());
+}
+
+#[test]
+fn the_same_rectangle_three_times() {
+	let rects = [rect(2, 2, 5, 4); 3];
+	assert_eq!(measure(&rects), Coverage { total_area: 60, union_area: 20, overlap_area: 20 });
+}
+
+#[test]
+fn touching_rectangles_do_not_overlap() {
+	let rects = [rect(0, 0, 10, 10), rect(10, 0, 10, 10), rect(0, 10, 20, 5)];
+	assert_eq!(union_area(&rects), 300);
+	assert_eq!(overlap_area(&rects), 0);
+}
+
+#[test]
+fn one_inside_another() {
+	let rects = [rect(0, 0, 10, 10), rect(2, 3, 4, 5)];
+	assert_eq!(measure(&rects), Coverage { total_area: 120, union_area: 100, overlap_area: 20 });
+}
+
+#[test]
+fn areas_past_u64() {
+	// each one is almost u64::MAX on its own, the total of three of them needs the u128
+	let huge = rect(0, 0, u32::MAX, u32::MAX);
+	let area = u128::from(u32::MAX) * u128::from(u32::MAX);
+	assert_eq!(measure(&[huge; 3]), Coverage { total_area: 3 * area, union_area: area, overlap_area: area });
+}
+
+#[test]
+fn the_parts_outside_bounds_are_ignored() {
+	let bounds = rect(10, 10, 10, 10);
+	// half of this one hangs out of the left side and the other one is nowhere near
+	assert_eq!(uncovered_area(&bounds, &[rect(5, 10, 10, 10), rect(100, 100, 5, 5)]), 50);
+	assert_eq!(uncovered_area(&bounds, &[rect(0, 0, 50, 50)]), 0);
+}
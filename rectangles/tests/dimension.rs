@@ -0,0 +1,48 @@
+// the same rectangle methods over the different Dimension types: wide edges and areas, signed corners and floats
+use rectangles::{Dimension, PositionedRectangle, Rectangle};
+
+#[test]
+fn integers_widen_so_edges_and_areas_do_not_overflow() {
+	assert_eq!(u32::MAX.widen(), u64::from(u32::MAX));
+	assert_eq!(<u32 as Dimension>::area(u32::MAX, u32::MAX), u64::from(u32::MAX) * u64::from(u32::MAX));
+	assert_eq!(<u8 as Dimension>::area(255, 255), 65025u16);
+	assert_eq!(<i32 as Dimension>::area(-3, 4), -12i64);
+	assert_eq!(u32::narrow(7u64), 7);
+	assert_eq!(i64::zero(), 0);
+
+	let rect = PositionedRectangle::new(u32::MAX, u32::MAX - 1, Rectangle::new(u32::MAX, 10));
+	assert_eq!(rect.right(), 2 * u64::from(u32::MAX));
+	assert_eq!(rect.bottom(), u64::from(u32::MAX) + 9);
+	assert_eq!(rect.area(), u64::from(u32::MAX) * 10);
+	assert!(rect.contains_point(u32::MAX, u32::MAX));
+}
+
+#[test]
+fn intersection_past_u32_max() {
+	let a = PositionedRectangle::new(u32::MAX - 10, 0, Rectangle::new(u32::MAX, 5));
+	let b = PositionedRectangle::new(u32::MAX - 4, 2, Rectangle::new(100, 100));
+	assert_eq!(a.intersection(&b), Some(PositionedRectangle::new(u32::MAX - 4, 2, Rectangle::new(100, 3))));
+}
+
+#[test]
+fn signed_corners() {
+	let a = PositionedRectangle::new(-10i32, -10, Rectangle::new(15, 5));
+	assert_eq!((a.right(), a.bottom()), (5i64, -5i64));
+	assert!(a.contains_point(-10, -6));
+	assert!(!a.contains_point(5, -6));
+	let b = PositionedRectangle::new(0, -8, Rectangle::new(10, 10));
+	assert_eq!(a.intersection(&b), Some(PositionedRectangle::new(0, -8, Rectangle::new(5, 3))));
+	assert_eq!(i32::MIN.widen() - i32::MAX.widen(), -(1i64 << 32) + 1);
+}
+
+#[test]
+fn floats() {
+	let a = PositionedRectangle::new(0.5f64, 0.25, Rectangle::new(2.0, 1.5));
+	assert_eq!(a.area(), 3.0);
+	assert_eq!(a.right(), 2.5);
+	let b = PositionedRectangle::new(2.0f64, 1.0, Rectangle::new(1.0, 1.0));
+	assert_eq!(a.intersection(&b), Some(PositionedRectangle::new(2.0, 1.0, Rectangle::new(0.5, 0.75))));
+	assert_eq!(Rectangle::new(1.5f32, 2.0).area(), 3.0f32);
+	assert_eq!(f32::zero(), 0.0);
+	assert!(Rectangle::new(2.0, 2.0).can_hold(&Rectangle::new(1.5, 1.5)));
+}
@@ -0,0 +1,121 @@
+// each search checked against trying every rectangle there is, on inputs small enough for that
+use proptest::prelude::*;
+use rectangles::free_space::{largest_empty_area, largest_empty_in_grid, largest_in_histogram};
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn rect(x: u32, y: u32, width: u32, height: u32) -> PositionedRectangle {
+	PositionedRectangle::new(x, y, Rectangle::new(width, height))
+}
+
+fn brute_histogram(heights: &[u32]) -> u64 {
+	let mut best = 0;
+	for first in 0..heights.len() {
+		for end in first + 1..=heights.len() {
+			let lowest = heights[first..end].iter().copied().min().unwrap_or(0);
+			best = best.max(u64::from(lowest) * (end - first) as u64);
+		}
+	}
+	best
+}
+
+// every rectangle on whole numbers inside bounds, with the ones clear of all obstacles kept
+fn brute_empty_area(bounds: &PositionedRectangle, obstacles: &[PositionedRectangle]) -> u64 {
+	let mut best = 0;
+	for y in bounds.y..bounds.y + bounds.height {
+		for x in bounds.x..bounds.x + bounds.width {
+			for height in 1..=bounds.y + bounds.height - y {
+				for width in 1..=bounds.x + bounds.width - x {
+					let candidate = rect(x, y, width, height);
+					if candidate.area() > best && obstacles.iter().all(|obstacle| !obstacle.intersects(&candidate)) {
+						best = candidate.area();
+					}
+				}
+			}
+		}
+	}
+	best
+}
+
+fn small() -> impl Strategy<Value = PositionedRectangle> {
+	(0..12u32, 0..12u32, 0..6u32, 0..6u32).prop_map(|(x, y, width, height)| rect(x, y, width, height))
+}
+
+proptest! {
+	#[test]
+	fn histogram_matches_brute_force(heights in prop::collection::vec(0..10u32, 0..12)) {
+		let found = largest_in_histogram(&heights);
+		prop_assert_eq!(found.map_or(0, |rect| rect.area()), brute_histogram(&heights));
+		if let Some(found) = found {
+			// every bar it stands on is at least that tall
+			let bars = &heights[found.x as usize..found.right() as usize];
+			prop_assert!(bars.iter().all(|&height| height >= found.height));
+		}
+	}
+
+	#[test]
+	fn grid_matches_brute_force(grid in prop::collection::vec(prop::collection::vec(prop::bool::weighted(0.3), 6), 0..6)) {
+		let obstacles: Vec<PositionedRectangle> = grid
+			.iter()
+			.enumerate()
+			.flat_map(|(y, row)| row.iter().enumerate().filter(|&(_, &taken)| taken).map(move |(x, _)| rect(x as u32, y as u32, 1, 1)))
+			.collect();
+		let bounds = rect(0, 0, 6, grid.len() as u32);
+		let found = largest_empty_in_grid(&grid);
+		prop_assert_eq!(found.map_or(0, |rect| rect.area()), brute_empty_area(&bounds, &obstacles));
+		if let Some(found) = found {
+			prop_assert!(bounds.contains(&found));
+			prop_assert!(obstacles.iter().all(|obstacle| !obstacle.intersects(&found)));
+		}
+	}
+
+	#[test]
+	fn empty_area_matches_brute_force(bounds in small(), obstacles in prop::collection::vec(small(), 0..6)) {
+		let found = largest_empty_area(&bounds, &obstacles);
+		prop_assert_eq!(found.map_or(0, |rect| rect.area()), brute_empty_area(&bounds, &obstacles));
+		if let Some(found) = found {
+			prop_assert!(bounds.contains(&found));
+			prop_assert!(obstacles.iter().all(|obstacle| !obstacle.intersects(&found)));
+		}
+	}
+}
+
+#[test]
+fn the_textbook_histogram() {
+	assert_eq!(largest_in_histogram(&[2, 1, 5, 6, 2, 3]), Some(rect(2, 0, 2, 5)));
+	assert_eq!(largest_in_histogram(&[0, 0]), None);
+	assert_eq!(largest_in_histogram(&[]), None);
+}
+
+#[test]
+fn the_first_of_equal_answers_wins() {
+	// two empty 2x2 squares, the higher one is found first
+	let grid = vec![vec![false, false, true], vec![false, false, true], vec![true, true, true], vec![false, false, true], vec![false, false, true]];
+	assert_eq!(largest_empty_in_grid(&grid), Some(rect(0, 0, 2, 2)));
+}
+
+#[test]
+fn short_rows_are_taken_past_their_end() {
+	let grid = vec![vec![false, false, false], vec![false]];
+	assert_eq!(largest_empty_in_grid(&grid), Some(rect(0, 0, 3, 1)));
+}
+
+#[test]
+fn covered_completely() {
+	let bounds = rect(10, 10, 20, 20);
+	assert_eq!(largest_empty_area(&bounds, &[rect(0, 0, 50, 50)]), None);
+	assert_eq!(largest_empty_area(&bounds, &[rect(10, 10, 10, 20), rect(20, 10, 10, 20)]), None);
+}
+
+#[test]
+fn obstacles_outside_bounds_are_ignored() {
+	let bounds = rect(10, 10, 20, 20);
+	assert_eq!(largest_empty_area(&bounds, &[rect(0, 0, 5, 5)]), Some(bounds));
+	// and an empty one inside doesn't block anything
+	assert_eq!(largest_empty_area(&bounds, &[rect(15, 15, 0, 10)]), Some(bounds));
+}
+
+#[test]
+fn bounds_past_u32_max_are_cut_off() {
+	let bounds = rect(u32::MAX - 10, 0, 20, 5);
+	assert_eq!(largest_empty_area(&bounds, &[]), Some(rect(u32::MAX - 10, 0, 10, 5)));
+}
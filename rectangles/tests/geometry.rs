@@ -0,0 +1,51 @@
+// what counts as empty and what intersects means for one
+use rectangles::spatial::QuadTree;
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn rect(x: u32, y: u32, width: u32, height: u32) -> PositionedRectangle {
+	PositionedRectangle::new(x, y, Rectangle::new(width, height))
+}
+
+#[test]
+fn zero_width_or_height_is_empty() {
+	assert!(rect(5, 5, 0, 3).is_empty());
+	assert!(rect(5, 5, 3, 0).is_empty());
+	assert!(rect(5, 5, 0, 0).is_empty());
+	assert!(!rect(5, 5, 1, 1).is_empty());
+	// and for signed sides, less than zero too
+	assert!(PositionedRectangle::new(-2, 0, Rectangle::new(-1, 4)).is_empty());
+}
+
+#[test]
+fn touching_edges_do_not_intersect() {
+	let a = rect(0, 0, 10, 10);
+	assert!(!a.intersects(&rect(10, 0, 5, 5)));
+	assert!(!a.intersects(&rect(0, 10, 5, 5)));
+	assert!(a.intersects(&rect(9, 9, 5, 5)));
+}
+
+#[test]
+fn empty_rectangles_intersect_nothing() {
+	let a = rect(0, 0, 10, 10);
+	// a line and a point right in the middle of a
+	let line = rect(5, 2, 0, 6);
+	let point = rect(5, 5, 0, 0);
+	for empty in [line, point] {
+		assert!(!a.intersects(&empty));
+		assert!(!empty.intersects(&a));
+		assert!(!empty.intersects(&empty));
+		assert_eq!(a.intersection(&empty), None);
+	}
+	// still inside a as far as contains goes, it only asks about the edges
+	assert!(a.contains(&line));
+}
+
+#[test]
+fn quadtree_queries_skip_empty_rectangles() {
+	let mut tree = QuadTree::new(rect(0, 0, 100, 100));
+	let solid = tree.insert(rect(10, 10, 20, 20));
+	let line = tree.insert(rect(15, 15, 0, 10));
+	assert_eq!(tree.query_range(&rect(0, 0, 50, 50)), [solid]);
+	assert!(tree.query_range(&rect(12, 12, 0, 0)).is_empty());
+	assert_eq!(tree.get(line), Some(rect(15, 15, 0, 10)));
+}
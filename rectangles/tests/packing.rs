@@ -0,0 +1,104 @@
+use rectangles::packing::{Heuristic, Packing};
+use rectangles::{PositionedRectangle, Rectangle};
+
+const HEURISTICS: [Heuristic; 2] = [Heuristic::Shelf, Heuristic::MaxRects];
+
+fn items() -> Vec<Rectangle> {
+	[(30, 20), (10, 45), (25, 25), (5, 5), (40, 10), (15, 30), (20, 20), (8, 12), (50, 5), (12, 8), (33, 17), (6, 40)]
+		.into_iter()
+		.map(|(width, height)| Rectangle::new(width, height))
+		.collect()
+}
+
+// everything pack promises about what it hands back, whatever the heuristic
+fn check(container: Rectangle, items: &[Rectangle], packing: &Packing) {
+	let bounds = PositionedRectangle::new(0, 0, container);
+	for placement in &packing.placements {
+		let item = items[placement.index];
+		let expected = if placement.rotated { item.rotated() } else { item };
+		assert_eq!(placement.position.size(), expected);
+		assert!(bounds.contains(&placement.position), "{placement:?} is outside {container:?}");
+	}
+	for (i, a) in packing.placements.iter().enumerate() {
+		for b in &packing.placements[i + 1..] {
+			assert!(!a.position.intersects(&b.position), "{a:?} overlaps {b:?}");
+		}
+	}
+
+	// every item is either placed or unplaced, once, and both lists are sorted
+	let mut indexes: Vec<usize> = packing.placements.iter().map(|placement| placement.index).collect();
+	assert!(indexes.is_sorted());
+	assert!(packing.unplaced.is_sorted());
+	indexes.extend(&packing.unplaced);
+	indexes.sort();
+	assert_eq!(indexes, (0..items.len()).collect::<Vec<usize>>());
+
+	let used: u64 = packing.placements.iter().map(|placement| placement.position.area()).sum();
+	assert_eq!(packing.wasted_area, container.area() - used);
+}
+
+#[test]
+fn placements_stay_inside_and_never_overlap() {
+	let items = items();
+	for container in [Rectangle::new(60, 60), Rectangle::new(100, 40), Rectangle::new(30, 200), Rectangle::new(200, 200)] {
+		for heuristic in HEURISTICS {
+			for allow_rotation in [false, true] {
+				check(container, &items, &container.pack(&items, heuristic, allow_rotation));
+			}
+		}
+	}
+}
+
+#[test]
+fn everything_fits_in_a_big_enough_container() {
+	let items = items();
+	let container = Rectangle::new(500, 500);
+	for heuristic in HEURISTICS {
+		let packing = container.pack(&items, heuristic, false);
+		assert!(packing.unplaced.is_empty(), "{heuristic:?}");
+		check(container, &items, &packing);
+	}
+}
+
+#[test]
+fn an_exact_fit_wastes_nothing() {
+	let items = vec![Rectangle::square(5); 4];
+	let container = Rectangle::new(10, 10);
+	for heuristic in HEURISTICS {
+		let packing = container.pack(&items, heuristic, false);
+		assert_eq!((packing.placements.len(), packing.wasted_area), (4, 0), "{heuristic:?}");
+	}
+}
+
+#[test]
+fn items_that_do_not_fit_are_left_out() {
+	let items = [Rectangle::new(5, 5), Rectangle::new(11, 2), Rectangle::new(3, 3)];
+	let container = Rectangle::new(10, 10);
+	for heuristic in HEURISTICS {
+		let packing = container.pack(&items, heuristic, false);
+		assert_eq!(packing.unplaced, [1], "{heuristic:?}");
+		check(container, &items, &packing);
+	}
+}
+
+#[test]
+fn rotation_lets_an_item_fit() {
+	let items = [Rectangle::new(10, 3)];
+	let container = Rectangle::new(3, 10);
+	for heuristic in HEURISTICS {
+		assert_eq!(container.pack(&items, heuristic, false).unplaced, [0]);
+		let packing = container.pack(&items, heuristic, true);
+		assert!(packing.unplaced.is_empty());
+		assert!(packing.placements[0].rotated);
+		check(container, &items, &packing);
+	}
+}
+
+#[test]
+fn nothing_to_pack() {
+	for heuristic in HEURISTICS {
+		let packing = Rectangle::new(10, 10).pack(&[], heuristic, true);
+		assert!(packing.placements.is_empty() && packing.unplaced.is_empty());
+		assert_eq!(packing.wasted_area, 100);
+	}
+}
@@ -0,0 +1,164 @@
+// the numbers are all small binary fractions so every comparison below is exact
+use rectangles::physics::{penetration, sweep, Body, Vector, World};
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn rect(x: f64, y: f64, width: f64, height: f64) -> PositionedRectangle<f64> {
+	PositionedRectangle::new(x, y, Rectangle::new(width, height))
+}
+
+#[test]
+fn touching_edges_are_not_a_collision() {
+	let a = rect(0.0, 0.0, 10.0, 10.0);
+	let b = rect(10.0, 0.0, 10.0, 10.0);
+	assert_eq!(penetration(&a, &b), None);
+	// sliding along b's edge without ever moving into it
+	assert_eq!(sweep(&a, Vector::new(0.0, 50.0), &b), None);
+}
+
+#[test]
+fn penetration_uses_the_shallower_axis() {
+	let a = rect(0.0, 0.0, 10.0, 10.0);
+	let b = rect(8.0, 1.0, 10.0, 10.0);
+	let contact = penetration(&a, &b).unwrap();
+	assert_eq!(contact.normal, Vector::new(-1.0, 0.0));
+	assert_eq!(contact.depth, 2.0);
+
+	let contact = penetration(&b, &a).unwrap();
+	assert_eq!(contact.normal, Vector::new(1.0, 0.0));
+}
+
+#[test]
+fn equal_overlap_on_both_axes_resolves_vertically() {
+	let a = rect(0.0, 0.0, 10.0, 10.0);
+	let b = rect(6.0, 6.0, 10.0, 10.0);
+	let contact = penetration(&a, &b).unwrap();
+	assert_eq!(contact.normal, Vector::new(0.0, -1.0));
+	assert_eq!(contact.depth, 4.0);
+}
+
+#[test]
+fn sweep_reports_time_and_side() {
+	let mover = rect(0.0, 0.0, 10.0, 10.0);
+	let wall = rect(30.0, -5.0, 4.0, 40.0);
+	let hit = sweep(&mover, Vector::new(40.0, 0.0), &wall).unwrap();
+	assert_eq!(hit.time, 0.5);
+	assert_eq!(hit.normal, Vector::new(-1.0, 0.0));
+}
+
+#[test]
+fn sweep_ignores_targets_out_of_reach_or_behind() {
+	let mover = rect(0.0, 0.0, 10.0, 10.0);
+	let wall = rect(30.0, 0.0, 4.0, 10.0);
+	// stops exactly against the wall, touching is not a hit
+	assert_eq!(sweep(&mover, Vector::new(20.0, 0.0), &wall), None);
+	assert_eq!(sweep(&mover, Vector::new(-40.0, 0.0), &wall), None);
+	assert_eq!(sweep(&mover, Vector::new(0.0, 0.0), &wall), None);
+}
+
+#[test]
+fn sweep_leaves_existing_overlaps_to_penetration() {
+	let mover = rect(0.0, 0.0, 10.0, 10.0);
+	let other = rect(5.0, 5.0, 10.0, 10.0);
+	assert_eq!(sweep(&mover, Vector::new(4.0, 4.0), &other), None);
+}
+
+#[test]
+fn exact_corner_hit_counts_as_vertical() {
+	let mover = rect(0.0, 0.0, 10.0, 10.0);
+	let block = rect(20.0, 20.0, 10.0, 10.0);
+	let hit = sweep(&mover, Vector::new(20.0, 20.0), &block).unwrap();
+	assert_eq!(hit.time, 0.5);
+	assert_eq!(hit.normal, Vector::new(0.0, -1.0));
+}
+
+#[test]
+fn grazing_a_corner_is_not_a_hit() {
+	// the mover's bottom right corner passes over the block's top left one, touching it at a single point halfway
+	let mover = rect(0.0, 10.0, 10.0, 10.0);
+	let block = rect(20.0, 10.0, 10.0, 10.0);
+	assert_eq!(sweep(&mover, Vector::new(20.0, -20.0), &block), None);
+}
+
+#[test]
+fn fast_bodies_do_not_tunnel_through_thin_walls() {
+	let mut world = World::new();
+	let bullet = world.add(Body::moving(rect(0.0, 0.0, 2.0, 2.0), Vector::new(10_000.0, 0.0)));
+	let wall = world.add(Body::fixed(rect(100.0, -50.0, 1.0, 100.0)));
+
+	let collisions = world.step(1.0);
+	assert_eq!(world.bodies[bullet].rect, rect(98.0, 0.0, 2.0, 2.0));
+	assert_eq!(world.bodies[bullet].velocity, Vector::new(0.0, 0.0));
+	assert_eq!(collisions.len(), 1);
+	assert_eq!(collisions[0].body, bullet);
+	assert_eq!(collisions[0].other, wall);
+	assert_eq!(collisions[0].normal, Vector::new(-1.0, 0.0));
+	assert_eq!(collisions[0].depth, 0.0);
+}
+
+#[test]
+fn landing_on_a_floor_slides_along_it() {
+	let mut world = World::new();
+	let ball = world.add(Body::moving(rect(0.0, 0.0, 4.0, 4.0), Vector::new(8.0, 16.0)));
+	world.add(Body::fixed(rect(-100.0, 8.0, 200.0, 4.0)));
+
+	let collisions = world.step(1.0);
+	// hits the floor a quarter of the way through, then keeps all of its sideways motion
+	assert_eq!(collisions[0].time, 0.25);
+	assert_eq!(collisions[0].normal, Vector::new(0.0, -1.0));
+	assert_eq!(world.bodies[ball].rect, rect(8.0, 4.0, 4.0, 4.0));
+	assert_eq!(world.bodies[ball].velocity, Vector::new(8.0, 0.0));
+
+	// resting on the floor is touching, not colliding
+	assert!(world.step(1.0).is_empty());
+	assert_eq!(world.bodies[ball].rect, rect(16.0, 4.0, 4.0, 4.0));
+}
+
+#[test]
+fn sliding_into_a_corner_stops_on_both_axes() {
+	let mut world = World::new();
+	let ball = world.add(Body::moving(rect(0.0, 0.0, 4.0, 4.0), Vector::new(16.0, 16.0)));
+	world.add(Body::fixed(rect(-100.0, 8.0, 200.0, 4.0)));
+	world.add(Body::fixed(rect(12.0, -100.0, 4.0, 200.0)));
+
+	let collisions = world.step(1.0);
+	assert_eq!(collisions.len(), 2);
+	assert_eq!(world.bodies[ball].rect, rect(8.0, 4.0, 4.0, 4.0));
+	assert_eq!(world.bodies[ball].velocity, Vector::new(0.0, 0.0));
+}
+
+#[test]
+fn overlapping_bodies_are_pushed_apart() {
+	let mut world = World::new();
+	let a = world.add(Body::moving(rect(0.0, 0.0, 10.0, 10.0), Vector::default()));
+	let b = world.add(Body::moving(rect(6.0, 0.0, 10.0, 10.0), Vector::default()));
+	let wall = world.add(Body::fixed(rect(-2.0, 20.0, 4.0, 4.0)));
+	let stuck = world.add(Body::moving(rect(0.0, 21.0, 4.0, 4.0), Vector::default()));
+
+	let collisions = world.step(1.0);
+	// two moving bodies split the distance
+	assert_eq!(world.bodies[a].rect, rect(-2.0, 0.0, 10.0, 10.0));
+	assert_eq!(world.bodies[b].rect, rect(8.0, 0.0, 10.0, 10.0));
+	// a fixed one doesn't budge so the other body takes the whole push
+	assert_eq!(world.bodies[wall].rect, rect(-2.0, 20.0, 4.0, 4.0));
+	assert_eq!(world.bodies[stuck].rect, rect(2.0, 21.0, 4.0, 4.0));
+	assert_eq!(collisions.iter().map(|collision| collision.depth).collect::<Vec<_>>(), vec![4.0, 2.0]);
+}
+
+#[test]
+fn stepping_is_deterministic() {
+	let build = || {
+		let mut world = World::new();
+		for i in 0..6 {
+			let offset = f64::from(i) * 3.0;
+			world.add(Body::moving(rect(offset, offset * 0.5, 4.0, 4.0), Vector::new(20.0 - offset, 7.5 + offset)));
+		}
+		world.add(Body::fixed(rect(-50.0, 40.0, 200.0, 8.0)));
+		world.add(Body::fixed(rect(45.0, -50.0, 8.0, 200.0)));
+		world
+	};
+	let (mut first, mut second) = (build(), build());
+	for _ in 0..20 {
+		assert_eq!(first.step(0.125), second.step(0.125));
+	}
+	assert_eq!(first.bodies, second.bodies);
+}
@@ -0,0 +1,90 @@
+/*
+	Property based tests: proptest makes up a few hundred random inputs per property and, when
+	one fails, shrinks it down to the smallest input that still fails before reporting it.
+	Sides are kept small in most of them so that holding, overlapping and touching actually
+	happen often instead of almost never.
+*/
+use proptest::prelude::*;
+use rectangles::coverage;
+use rectangles::parse::parse_rectangles;
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn rectangle() -> impl Strategy<Value = Rectangle> {
+	(0..20u32, 0..20u32).prop_map(|(width, height)| Rectangle::new(width, height))
+}
+
+fn positioned() -> impl Strategy<Value = PositionedRectangle> {
+	(0..40u32, 0..40u32, rectangle()).prop_map(|(x, y, size)| PositionedRectangle::new(x, y, size))
+}
+
+proptest! {
+	#[test]
+	fn can_hold_is_irreflexive(rect in rectangle()) {
+		prop_assert!(!rect.can_hold(&rect));
+	}
+
+	#[test]
+	fn can_hold_is_transitive(a in rectangle(), b in rectangle(), c in rectangle()) {
+		if a.can_hold(&b) && b.can_hold(&c) {
+			prop_assert!(a.can_hold(&c));
+		}
+	}
+
+	#[test]
+	fn can_hold_is_never_mutual(a in rectangle(), b in rectangle()) {
+		prop_assert!(!(a.can_hold(&b) && b.can_hold(&a)));
+	}
+
+	#[test]
+	fn square_area_is_size_squared(size in any::<u32>()) {
+		prop_assert_eq!(Rectangle::square(size).area(), u64::from(size) * u64::from(size));
+	}
+
+	#[test]
+	fn area_never_overflows(width in any::<u32>(), height in any::<u32>()) {
+		prop_assert_eq!(u128::from(Rectangle::new(width, height).area()), u128::from(width) * u128::from(height));
+	}
+
+	#[test]
+	fn intersection_is_commutative(a in positioned(), b in positioned()) {
+		prop_assert_eq!(a.intersection(&b), b.intersection(&a));
+	}
+
+	#[test]
+	fn intersection_lies_inside_both(a in positioned(), b in positioned()) {
+		if let Some(common) = a.intersection(&b) {
+			prop_assert!(a.contains(&common) && b.contains(&common));
+			prop_assert!(common.area() > 0);
+		} else {
+			prop_assert!(!a.intersects(&b));
+		}
+	}
+
+	#[test]
+	fn union_area_is_between_largest_member_and_total(rects in prop::collection::vec(positioned(), 0..12)) {
+		let measured = coverage::measure(&rects);
+		let largest = rects.iter().map(|rect| u128::from(rect.area())).max().unwrap_or(0);
+		prop_assert!(measured.union_area >= largest);
+		prop_assert!(measured.union_area <= measured.total_area);
+		prop_assert!(measured.overlap_area <= measured.union_area);
+	}
+
+	#[test]
+	fn union_area_does_not_depend_on_order(mut rects in prop::collection::vec(positioned(), 0..12)) {
+		let forwards = coverage::union_area(&rects);
+		rects.reverse();
+		prop_assert_eq!(coverage::union_area(&rects), forwards);
+	}
+
+	#[test]
+	fn display_and_parse_round_trip(rects in prop::collection::vec((any::<u32>(), any::<u32>()), 0..20)) {
+		let rects: Vec<Rectangle> = rects.into_iter().map(|(width, height)| Rectangle::new(width, height)).collect();
+		let text: String = rects.iter().map(|rect| format!("{rect}\n")).collect();
+		prop_assert_eq!(parse_rectangles(&text).unwrap(), rects);
+	}
+
+	#[test]
+	fn parsing_never_panics(text in "\\PC*") {
+		let _ = parse_rectangles(&text);
+	}
+}
@@ -0,0 +1,96 @@
+use std::{env, fs};
+
+use rectangles::render::{ascii, svg, write_svg};
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn rect(x: u32, y: u32, width: u32, height: u32) -> PositionedRectangle {
+	PositionedRectangle::new(x, y, Rectangle::new(width, height))
+}
+
+fn labels(names: &[&str]) -> Vec<String> {
+	names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn side_by_side() {
+	let drawn = ascii(&[rect(0, 0, 10, 6), rect(10, 0, 10, 6)], &[], 80, 40);
+	assert_eq!(
+		drawn,
+		"\
+┌────────┐┌────────┐
+│1       ││2       │
+└────────┘└────────┘
+"
+	);
+}
+
+#[test]
+fn overlaps_are_shaded() {
+	let drawn = ascii(&[rect(0, 0, 20, 10), rect(6, 4, 20, 10)], &labels(&["a", "b"]), 80, 40);
+	assert_eq!(
+		drawn,
+		"\
+┌──────────────────┐
+│a                 │
+│     ┌────────────┼─────┐
+│     │b▒▒▒▒▒▒▒▒▒▒▒│     │
+└─────┼────────────┘     │
+      │                  │
+      └──────────────────┘
+"
+	);
+}
+
+#[test]
+fn sharing_an_edge_is_not_an_overlap() {
+	// y 0..3 and 3..6 both land in the second row of cells, and the third rectangle shares
+	// the right edge of both, but none of them have any area in common
+	let drawn = ascii(&[rect(0, 0, 10, 3), rect(0, 3, 10, 3), rect(10, 0, 3, 6)], &[], 80, 40);
+	assert!(!drawn.contains('▒'), "{drawn}");
+}
+
+#[test]
+fn tall_rectangles_are_scaled_to_the_row_limit() {
+	let drawn = ascii(&[rect(0, 0, 1, 4_000_000_000)], &[], 80, 40);
+	assert_eq!(drawn.lines().count(), 40);
+	let drawn = ascii(&[rect(0, 0, 1, 4_000_000_000)], &[], 80, 5);
+	assert_eq!(drawn, "│\n│\n│\n│\n│\n");
+}
+
+#[test]
+fn wide_rectangles_are_scaled_to_the_column_limit() {
+	let drawn = ascii(&[rect(0, 0, 100, 12)], &[], 10, 40);
+	assert!(drawn.lines().all(|line| line.chars().count() <= 10), "{drawn}");
+}
+
+#[test]
+fn nothing_to_draw() {
+	assert_eq!(ascii(&[], &[], 80, 40), "");
+}
+
+#[test]
+fn svg_marks_the_overlap_and_escapes_labels() {
+	let drawn = svg(&[rect(0, 0, 4, 4), rect(2, 2, 4, 4)], &labels(&["<a>"]));
+	assert!(drawn.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 6 6\""));
+	assert_eq!(drawn.matches("<rect ").count(), 3);
+	assert!(drawn.contains("<rect class=\"overlap\" x=\"2\" y=\"2\" width=\"2\" height=\"2\""));
+	assert!(drawn.contains(">&lt;a&gt;</text>"));
+	// the second rectangle has no label so it gets its position
+	assert!(drawn.contains(">2</text>"));
+	assert!(drawn.ends_with("</svg>\n"));
+}
+
+#[test]
+fn writing_an_svg_file() {
+	let path = env::temp_dir().join(format!("rectangles-render-{}.svg", std::process::id()));
+	let rects = [rect(0, 0, 4, 4), rect(2, 2, 4, 4)];
+	write_svg(&path, &rects, &labels(&["a", "b"])).unwrap();
+	assert_eq!(fs::read_to_string(&path).unwrap(), svg(&rects, &labels(&["a", "b"])));
+	// a second write replaces the first
+	write_svg(&path, &rects[..1], &[]).unwrap();
+	assert_eq!(fs::read_to_string(&path).unwrap(), svg(&rects[..1], &[]));
+	fs::remove_file(&path).unwrap();
+
+	let missing = env::temp_dir().join(format!("rectangles-render-missing-{}", std::process::id())).join("layout.svg");
+	assert!(write_svg(missing, &rects, &[]).is_err());
+}
@@ -0,0 +1,135 @@
+use rectangles::scaling::{Affine, AspectRatio, Orientation, Rounding};
+use rectangles::{PositionedRectangle, Rectangle};
+
+const ROUNDINGS: [Rounding; 4] = [Rounding::Nearest, Rounding::Down, Rounding::Up, Rounding::NearestNonZero];
+
+#[test]
+fn rounding_one_and_a_half() {
+	let rect = Rectangle::new(3, 5);
+	assert_eq!(rect.scale(0.5, Rounding::Nearest), Rectangle::new(2, 3));
+	assert_eq!(rect.scale(0.5, Rounding::Down), Rectangle::new(1, 2));
+	assert_eq!(rect.scale(0.5, Rounding::Up), Rectangle::new(2, 3));
+}
+
+#[test]
+fn nearest_non_zero_keeps_tiny_sides() {
+	let rect = Rectangle::new(4, 0);
+	assert_eq!(rect.scale(0.1, Rounding::Nearest), Rectangle::new(0, 0));
+	// the width wasn't 0 so it stays at least 1, the height was
+	assert_eq!(rect.scale(0.1, Rounding::NearestNonZero), Rectangle::new(1, 0));
+}
+
+#[test]
+fn out_of_range_results_are_clamped() {
+	assert_eq!(Rectangle::new(u32::MAX, 10).scale(2.0, Rounding::Nearest), Rectangle::new(u32::MAX, 20));
+	assert_eq!(Rectangle::new(10, 10).scale(-1.0, Rounding::Nearest), Rectangle::square(0));
+	assert_eq!(Rectangle::new(10, 10).scale(f64::NAN, Rounding::Up), Rectangle::square(0));
+}
+
+#[test]
+fn fit_keeps_the_aspect_ratio_inside_the_container() {
+	let photo = Rectangle::new(1920, 1080);
+	assert_eq!(photo.scale_to_fit(&Rectangle::square(800), Rounding::Nearest), Rectangle::new(800, 450));
+	assert_eq!(photo.rotated().scale_to_fit(&Rectangle::square(800), Rounding::Nearest), Rectangle::new(450, 800));
+	// and upwards too
+	assert_eq!(Rectangle::new(16, 9).scale_to_fit(&Rectangle::new(1000, 1000), Rounding::Down), Rectangle::new(1000, 562));
+
+	for container in [Rectangle::new(333, 777), Rectangle::new(1, 1), Rectangle::new(1921, 1081)] {
+		for rounding in ROUNDINGS {
+			let fitted = photo.scale_to_fit(&container, rounding);
+			assert!(container.can_fit(&fitted), "{fitted:?} in {container:?} with {rounding:?}");
+		}
+	}
+}
+
+#[test]
+fn fit_with_a_zero_side() {
+	let container = Rectangle::new(100, 50);
+	assert_eq!(Rectangle::new(0, 10).scale_to_fit(&container, Rounding::Nearest), Rectangle::new(0, 50));
+	assert_eq!(Rectangle::new(10, 0).scale_to_fit(&container, Rounding::Nearest), Rectangle::new(100, 0));
+	assert_eq!(Rectangle::square(0).scale_to_fit(&container, Rounding::Nearest), Rectangle::square(0));
+}
+
+#[test]
+fn fill_covers_the_container_and_crops_the_middle() {
+	let photo = Rectangle::new(1920, 1080);
+	let fill = photo.scale_to_fill(&Rectangle::square(800), Rounding::Nearest);
+	assert_eq!(fill.scaled, Rectangle::new(1422, 800));
+	assert_eq!(fill.crop, PositionedRectangle::new(311, 0, Rectangle::square(800)));
+	// 311 / (800 / 1080) rounded up and 1111 / (800 / 1080) rounded down
+	assert_eq!(fill.source_crop, PositionedRectangle::new(420, 0, Rectangle::new(1079, 1080)));
+
+	let source = PositionedRectangle::new(0, 0, photo);
+	for container in [Rectangle::new(333, 777), Rectangle::new(1, 1), Rectangle::new(4000, 100)] {
+		for rounding in ROUNDINGS {
+			let fill = photo.scale_to_fill(&container, rounding);
+			assert!(fill.scaled.width >= container.width && fill.scaled.height >= container.height);
+			assert_eq!(fill.crop.size(), container);
+			assert!(PositionedRectangle::new(0, 0, fill.scaled).contains(&fill.crop));
+			assert!(source.contains(&fill.source_crop), "{:?} reads past {photo:?}", fill.source_crop);
+		}
+	}
+}
+
+#[test]
+fn fill_with_a_zero_side_covers_nothing() {
+	let fill = Rectangle::new(0, 10).scale_to_fill(&Rectangle::square(5), Rounding::Nearest);
+	assert_eq!(fill.scaled, Rectangle::new(0, 10));
+	assert!(fill.crop.is_empty() && fill.source_crop.is_empty());
+}
+
+#[test]
+fn orientations() {
+	assert_eq!(Rectangle::new(3, 2).orientation(), Orientation::Landscape);
+	assert_eq!(Rectangle::new(2, 3).orientation(), Orientation::Portrait);
+	assert_eq!(Rectangle::square(7).orientation(), Orientation::Square);
+}
+
+#[test]
+fn named_aspect_ratios() {
+	assert_eq!(Rectangle::new(1920, 1080).aspect_class(), AspectRatio::SixteenNine);
+	assert_eq!(Rectangle::new(1080, 1920).aspect_class(), AspectRatio::SixteenNine);
+	assert_eq!(Rectangle::new(640, 480).aspect_class(), AspectRatio::FourThree);
+	assert_eq!(Rectangle::new(6000, 4000).aspect_class(), AspectRatio::ThreeTwo);
+	assert_eq!(Rectangle::new(1680, 1050).aspect_class(), AspectRatio::SixteenTen);
+	assert_eq!(Rectangle::new(2560, 1080).aspect_class(), AspectRatio::TwentyOneNine);
+	assert_eq!(Rectangle::new(21, 9).aspect_class(), AspectRatio::TwentyOneNine);
+	// within 1% of square
+	assert_eq!(Rectangle::new(1005, 1000).aspect_class(), AspectRatio::Square);
+	assert_eq!(Rectangle::new(5, 2).aspect_class(), AspectRatio::Other(2.5));
+	assert_eq!(Rectangle::new(0, 5).aspect_class(), AspectRatio::Other(f64::INFINITY));
+}
+
+#[test]
+fn golden_beats_sixteen_ten_when_it_is_closer() {
+	// 1.618 is within 1% of both 1.6 and the golden ratio, the closer one wins
+	assert_eq!(Rectangle::new(1618, 1000).aspect_class(), AspectRatio::Golden);
+	assert_eq!(Rectangle::new(1600, 1000).aspect_class(), AspectRatio::SixteenTen);
+}
+
+fn rect(x: f64, y: f64, width: f64, height: f64) -> PositionedRectangle<f64> {
+	PositionedRectangle::new(x, y, Rectangle::new(width, height))
+}
+
+#[test]
+fn affine_transforms() {
+	let r = rect(1.0, 2.0, 3.0, 4.0);
+	assert_eq!(Affine::identity().apply(&r), r);
+	assert_eq!(Affine::default(), Affine::identity());
+	assert_eq!(Affine::scale(2.0, 0.5).apply(&r), rect(2.0, 1.0, 6.0, 2.0));
+	assert_eq!(Affine::translate(-1.0, 10.0).apply(&r), rect(0.0, 12.0, 3.0, 4.0));
+	// a mirror flips the rectangle over the axis and keeps the sides positive
+	assert_eq!(Affine::scale(-1.0, 1.0).apply(&r), rect(-4.0, 2.0, 3.0, 4.0));
+}
+
+#[test]
+fn then_is_the_same_as_applying_one_after_the_other() {
+	let first = Affine { scale_x: 2.0, scale_y: -0.5, translate_x: 3.0, translate_y: 1.0 };
+	let second = Affine { scale_x: -4.0, scale_y: 8.0, translate_x: 0.25, translate_y: -2.0 };
+	let r = rect(1.0, 2.0, 3.0, 4.0);
+	assert_eq!(first.then(&second).apply(&r), second.apply(&first.apply(&r)));
+	assert_eq!(first.then(&second).apply_point(5.0, 7.0), {
+		let (x, y) = first.apply_point(5.0, 7.0);
+		second.apply_point(x, y)
+	});
+}
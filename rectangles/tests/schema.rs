@@ -0,0 +1,94 @@
+// cargo test --features serde
+#![cfg(feature = "serde")]
+
+use rectangles::schema::{Layout, SchemaError, CURRENT_VERSION};
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn sample() -> Layout {
+	Layout::new(
+		vec![Rectangle::new(30, 50), Rectangle::square(3), Rectangle::new(u32::MAX, 1)],
+		vec![PositionedRectangle::new(0, 0, Rectangle::new(10, 40)), PositionedRectangle::new(60, 45, Rectangle::square(7))],
+	)
+}
+
+#[test]
+fn json_round_trip() {
+	let layout = sample();
+	let json = serde_json::to_string(&layout).unwrap();
+	assert_eq!(serde_json::from_str::<Layout>(&json).unwrap(), layout);
+}
+
+#[test]
+fn toml_round_trip() {
+	let layout = sample();
+	let text = toml::to_string(&layout).unwrap();
+	assert_eq!(toml::from_str::<Layout>(&text).unwrap(), layout);
+}
+
+#[test]
+fn signed_and_float_layouts_round_trip() {
+	let signed: Layout<i32> = Layout::new(vec![Rectangle::square(4)], vec![PositionedRectangle::new(-20, -5, Rectangle::new(3, 9))]);
+	let json = serde_json::to_string(&signed).unwrap();
+	assert_eq!(serde_json::from_str::<Layout<i32>>(&json).unwrap(), signed);
+
+	let float: Layout<f64> = Layout::new(vec![Rectangle::new(210.0, 297.0)], vec![PositionedRectangle::new(0.5, 1.25, Rectangle::new(2.5, 0.75))]);
+	let text = toml::to_string(&float).unwrap();
+	assert_eq!(toml::from_str::<Layout<f64>>(&text).unwrap(), float);
+}
+
+#[test]
+fn the_schema_is_plain_maps_with_a_version() {
+	let layout = Layout::new(vec![Rectangle::square(3)], vec![PositionedRectangle::new(1, 2, Rectangle::new(3, 4))]);
+	assert_eq!(
+		serde_json::to_string(&layout).unwrap(),
+		format!(r#"{{"version":{CURRENT_VERSION},"rectangles":[{{"width":3,"height":3}}],"positioned":[{{"x":1,"y":2,"width":3,"height":4}}]}}"#)
+	);
+}
+
+#[test]
+fn missing_lists_default_to_empty() {
+	let layout: Layout = serde_json::from_str(r#"{"version":1}"#).unwrap();
+	assert!(layout.rectangles.is_empty() && layout.positioned.is_empty());
+}
+
+#[test]
+fn zero_sides_are_rejected() {
+	let error = serde_json::from_str::<Layout>(r#"{"version":1,"rectangles":[{"width":0,"height":5}]}"#).unwrap_err();
+	assert!(error.to_string().contains(&SchemaError::NotPositive("width").to_string()), "{error}");
+
+	let error = toml::from_str::<Layout>("version = 1\n[[positioned]]\nx = 0\ny = 0\nwidth = 4\nheight = 0\n").unwrap_err();
+	assert!(error.to_string().contains(&SchemaError::NotPositive("height").to_string()), "{error}");
+}
+
+#[test]
+fn negative_and_nan_sides_are_rejected() {
+	assert!(serde_json::from_str::<Rectangle<i32>>(r#"{"width":-3,"height":5}"#).is_err());
+	assert!(serde_json::from_str::<Rectangle>(r#"{"width":-3,"height":5}"#).is_err());
+	assert!(toml::from_str::<Rectangle<f64>>("width = nan\nheight = 2.0\n").is_err());
+	// a negative position is fine, only the size has to be positive
+	assert!(serde_json::from_str::<PositionedRectangle<i32>>(r#"{"x":-3,"y":-4,"width":1,"height":1}"#).is_ok());
+}
+
+#[test]
+fn unknown_versions_are_rejected() {
+	let error = serde_json::from_str::<Layout>(r#"{"version":2,"rectangles":[]}"#).unwrap_err();
+	assert!(error.to_string().contains(&SchemaError::UnsupportedVersion(2).to_string()), "{error}");
+}
+
+#[test]
+fn empty_rectangles_are_refused_when_saving() {
+	// a zero weight gets an empty cell, which would save as a file nothing can load
+	let cells = Rectangle::new(40, 30).treemap(&[3.0, 0.0, 1.0]);
+	let layout: Layout<f64> = Layout::new(vec![], cells.clone());
+	let error = serde_json::to_string(&layout).unwrap_err();
+	assert!(error.to_string().contains(&SchemaError::NotPositive("width").to_string()), "{error}");
+	assert!(toml::to_string(&layout).is_err());
+	assert!(serde_json::to_string(&Layout::new(vec![Rectangle::new(3, 0)], vec![])).is_err());
+
+	// without the empty cell it saves and loads back the same
+	let kept: Vec<PositionedRectangle<f64>> = cells.into_iter().filter(|cell| cell.area() > 0.0).collect();
+	assert_eq!(kept.len(), 2);
+	let layout = Layout::new(vec![], kept);
+	let json = serde_json::to_string(&layout).unwrap();
+	assert_eq!(serde_json::from_str::<Layout<f64>>(&json).unwrap(), layout);
+}
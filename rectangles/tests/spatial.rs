@@ -0,0 +1,82 @@
+/*
+	Everything the quadtree answers checked against just looping over every rectangle. The tree
+	covers 0..100 each way and rectangles go up to 120, so some stick out of it, and there are
+	enough of them that the tree has to split a few levels down.
+*/
+use proptest::prelude::*;
+use rectangles::spatial::QuadTree;
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn positioned() -> impl Strategy<Value = PositionedRectangle> {
+	(0..100u32, 0..100u32, 0..20u32, 0..20u32)
+		.prop_map(|(x, y, width, height)| PositionedRectangle::new(x, y, Rectangle::new(width, height)))
+}
+
+// the tree with every rectangle in it and every third one taken out again, plus the ones still in
+fn build(rects: &[PositionedRectangle]) -> (QuadTree, Vec<(usize, PositionedRectangle)>) {
+	let mut tree = QuadTree::new(PositionedRectangle::new(0, 0, Rectangle::new(100, 100)));
+	let ids: Vec<usize> = rects.iter().map(|&rect| tree.insert(rect)).collect();
+	let mut live = Vec::new();
+	for (index, (&rect, id)) in rects.iter().zip(ids).enumerate() {
+		if index % 3 == 0 {
+			assert_eq!(tree.remove(id), Some(rect));
+		} else {
+			live.push((id, rect));
+		}
+	}
+	(tree, live)
+}
+
+proptest! {
+	#[test]
+	fn query_point_matches_brute_force(rects in prop::collection::vec(positioned(), 0..200), x in 0..120u32, y in 0..120u32) {
+		let (tree, live) = build(&rects);
+		let expected: Vec<usize> = live.iter().filter(|(_, rect)| rect.contains_point(x, y)).map(|&(id, _)| id).collect();
+		prop_assert_eq!(tree.query_point(x, y), expected);
+	}
+
+	#[test]
+	fn query_range_matches_brute_force(rects in prop::collection::vec(positioned(), 0..200), region in positioned()) {
+		let (tree, live) = build(&rects);
+		let expected: Vec<usize> = live.iter().filter(|(_, rect)| rect.intersects(&region)).map(|&(id, _)| id).collect();
+		prop_assert_eq!(tree.query_range(&region), expected);
+	}
+
+	#[test]
+	fn nearest_matches_brute_force(rects in prop::collection::vec(positioned(), 0..200), x in 0..120u32, y in 0..120u32) {
+		let (tree, live) = build(&rects);
+		let expected = live.iter().min_by_key(|&&(id, rect)| (rect.distance_squared_to_point(x, y), id)).map(|&(id, _)| id);
+		prop_assert_eq!(tree.nearest(x, y), expected);
+	}
+
+	#[test]
+	fn len_counts_what_is_left(rects in prop::collection::vec(positioned(), 0..200)) {
+		let (tree, live) = build(&rects);
+		prop_assert_eq!(tree.len(), live.len());
+		for (id, rect) in live {
+			prop_assert_eq!(tree.get(id), Some(rect));
+		}
+	}
+}
+
+#[test]
+fn removed_ids_are_gone() {
+	let mut tree = QuadTree::new(PositionedRectangle::new(0, 0, Rectangle::new(10, 10)));
+	let id = tree.insert(PositionedRectangle::new(1, 1, Rectangle::new(2, 2)));
+	assert_eq!(tree.query_point(1, 1), [id]);
+	assert!(tree.remove(id).is_some());
+	assert_eq!(tree.remove(id), None);
+	assert_eq!(tree.get(id), None);
+	assert!(tree.query_point(1, 1).is_empty());
+	assert!(tree.is_empty());
+	assert_eq!(tree.nearest(1, 1), None);
+}
+
+#[test]
+fn a_tree_at_the_far_edge_of_u32() {
+	let bounds = PositionedRectangle::new(u32::MAX - 10, u32::MAX - 10, Rectangle::new(10, 10));
+	let mut tree = QuadTree::new(bounds);
+	let ids: Vec<usize> = (0..20).map(|_| tree.insert(PositionedRectangle::new(u32::MAX - 3, u32::MAX - 3, Rectangle::new(2, 2)))).collect();
+	assert_eq!(tree.query_point(u32::MAX - 2, u32::MAX - 2), ids);
+	assert_eq!(tree.query_range(&bounds), ids);
+}
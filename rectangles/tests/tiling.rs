@@ -0,0 +1,126 @@
+use rectangles::tiling::{Cut, CutError, Remainder, TileOrder};
+use rectangles::{PositionedRectangle, Rectangle};
+
+fn total_area(tiles: impl IntoIterator<Item = PositionedRectangle>) -> u64 {
+	tiles.into_iter().map(|tile| tile.area()).sum()
+}
+
+fn assert_no_overlaps(tiles: &[PositionedRectangle]) {
+	for (i, a) in tiles.iter().enumerate() {
+		for b in &tiles[i + 1..] {
+			assert!(!a.intersects(b), "{a:?} overlaps {b:?}");
+		}
+	}
+}
+
+const SIZES: [(u32, u32); 5] = [(1, 1), (7, 5), (10, 10), (13, 29), (64, 3)];
+
+#[test]
+fn grid_tiles_add_up_to_the_rectangle() {
+	for (width, height) in SIZES {
+		let rect = Rectangle::new(width, height);
+		for (columns, rows) in [(1, 1), (2, 3), (3, 2), (width, height)] {
+			if columns > width || rows > height {
+				continue;
+			}
+			let tiles: Vec<PositionedRectangle> = rect.grid(columns, rows, TileOrder::RowMajor).collect();
+			assert_eq!(tiles.len() as u32, columns * rows);
+			assert_eq!(total_area(tiles.iter().copied()), rect.area(), "{rect:?} in {columns}x{rows}");
+			assert_no_overlaps(&tiles);
+		}
+	}
+}
+
+#[test]
+fn grid_tiles_differ_by_at_most_a_pixel() {
+	let tiles: Vec<PositionedRectangle> = Rectangle::new(10, 7).grid(3, 2, TileOrder::RowMajor).collect();
+	let widths: Vec<u32> = tiles.iter().take(3).map(|tile| tile.width).collect();
+	assert_eq!(widths, [4, 3, 3]);
+	assert_eq!((tiles[0].height, tiles[3].height), (4, 3));
+}
+
+#[test]
+fn grid_with_more_columns_than_pixels_is_empty() {
+	assert_eq!(Rectangle::new(3, 3).grid(4, 1, TileOrder::RowMajor).count(), 0);
+	assert_eq!(Rectangle::new(3, 3).grid(0, 1, TileOrder::ZOrder).count(), 0);
+}
+
+#[test]
+fn fixed_tiles_add_up_to_the_rectangle() {
+	let canvas = PositionedRectangle::new(0, 0, Rectangle::new(0, 0));
+	for (width, height) in SIZES {
+		let rect = Rectangle::new(width, height);
+		let canvas = PositionedRectangle { width, height, ..canvas };
+		for tile in [Rectangle::new(1, 1), Rectangle::new(3, 4), Rectangle::new(8, 8), Rectangle::new(100, 100)] {
+			for remainder in [Remainder::Clip, Remainder::Merge] {
+				let tiles: Vec<PositionedRectangle> = rect.tiles(tile, remainder, TileOrder::RowMajor).collect();
+				assert_eq!(total_area(tiles.iter().copied()), rect.area(), "{rect:?} in {tile:?} tiles, {remainder:?}");
+				assert_no_overlaps(&tiles);
+			}
+			// padded tiles hang over the edge, what's left inside the canvas is the whole canvas
+			let padded = rect.tiles(tile, Remainder::Pad, TileOrder::RowMajor);
+			assert_eq!(total_area(padded.filter_map(|tile| tile.intersection(&canvas))), rect.area());
+		}
+	}
+}
+
+#[test]
+fn zero_sized_tiles_give_nothing() {
+	assert_eq!(Rectangle::new(10, 10).tiles(Rectangle::new(0, 5), Remainder::Clip, TileOrder::RowMajor).count(), 0);
+}
+
+// column bits in the even places, row bits in the odd ones
+fn morton(tile: &PositionedRectangle) -> u64 {
+	let spread = |value: u32| (0..32).fold(0u64, |code, bit| code | (u64::from(value >> bit & 1) << (2 * bit)));
+	spread(tile.x) | spread(tile.y) << 1
+}
+
+#[test]
+fn z_order_is_the_same_tiles_sorted_along_the_curve() {
+	for (columns, rows) in [(1, 1), (4, 4), (5, 3), (3, 7), (16, 1)] {
+		let rect = Rectangle::new(columns, rows);
+		let mut expected: Vec<PositionedRectangle> = rect.grid(columns, rows, TileOrder::RowMajor).collect();
+		expected.sort_by_key(morton);
+		let z_order: Vec<PositionedRectangle> = rect.grid(columns, rows, TileOrder::ZOrder).collect();
+		assert_eq!(z_order, expected, "{columns}x{rows}");
+	}
+}
+
+#[test]
+fn z_order_on_a_huge_grid_starts_straight_away() {
+	// u32::MAX squared tiles, a list of them all wouldn't fit in memory
+	let mut tiles = Rectangle::new(u32::MAX, u32::MAX).grid(u32::MAX, u32::MAX, TileOrder::ZOrder);
+	assert_eq!(tiles.size_hint().0 as u64, u64::from(u32::MAX) * u64::from(u32::MAX));
+	let corners: Vec<(u32, u32)> = tiles.by_ref().take(5).map(|tile| (tile.x, tile.y)).collect();
+	assert_eq!(corners, [(0, 0), (1, 0), (0, 1), (1, 1), (2, 0)]);
+}
+
+#[test]
+fn guillotine_pieces_add_up_to_the_rectangle() {
+	let rect = PositionedRectangle::new(5, 7, Rectangle::new(10, 6));
+	for cut in [Cut::Vertical(0), Cut::Vertical(4), Cut::Vertical(10), Cut::Horizontal(1), Cut::Horizontal(6)] {
+		let (first, second) = rect.guillotine(cut).unwrap();
+		assert_eq!(first.area() + second.area(), rect.area(), "{cut:?}");
+		assert!(!first.intersects(&second));
+	}
+	let (left, right) = rect.guillotine(Cut::Vertical(4)).unwrap();
+	assert_eq!(left, PositionedRectangle::new(5, 7, Rectangle::new(4, 6)));
+	assert_eq!(right, PositionedRectangle::new(9, 7, Rectangle::new(6, 6)));
+}
+
+#[test]
+fn cutting_past_the_edge_leaves_an_empty_piece() {
+	let (top, bottom) = Rectangle::new(10, 6).guillotine(Cut::Horizontal(50));
+	assert_eq!(top, PositionedRectangle::new(0, 0, Rectangle::new(10, 6)));
+	assert_eq!(bottom, PositionedRectangle::new(0, 6, Rectangle::new(10, 0)));
+}
+
+#[test]
+fn guillotine_near_u32_max() {
+	let rect = PositionedRectangle::new(u32::MAX - 5, u32::MAX - 5, Rectangle::new(10, 10));
+	assert_eq!(rect.guillotine(Cut::Vertical(8)), Err(CutError::PastU32Max(u64::from(u32::MAX) + 3)));
+	assert_eq!(rect.guillotine(Cut::Horizontal(6)), Err(CutError::PastU32Max(u64::from(u32::MAX) + 1)));
+	// the second piece can still start right on u32::MAX
+	let (left, right) = rect.guillotine(Cut::Vertical(5)).unwrap();
+	assert_eq!((left.width, right.x, right.width), (5, u32::MAX, 5));
+}
@@ -0,0 +1,81 @@
+use rectangles::coverage;
+use rectangles::{PositionedRectangle, Rectangle};
+
+// close enough for f64 sums of a handful of numbers this size
+fn close(a: f64, b: f64) -> bool {
+	(a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0)
+}
+
+const WEIGHTS: [f64; 9] = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0, 0.5, 7.25];
+
+#[test]
+fn areas_are_proportional_to_the_weights() {
+	for bounds in [Rectangle::new(6, 4), Rectangle::new(100, 30), Rectangle::new(7, 500), Rectangle::square(1)] {
+		let layout = bounds.treemap(&WEIGHTS);
+		let total: f64 = WEIGHTS.iter().sum();
+		for (rect, weight) in layout.iter().zip(WEIGHTS) {
+			let expected = weight / total * bounds.area() as f64;
+			assert!(close(rect.area(), expected), "{bounds:?}: {rect:?} should have an area of {expected}");
+		}
+	}
+}
+
+#[test]
+fn pieces_fill_the_bounds_without_overlapping() {
+	let bounds = Rectangle::new(100, 30);
+	let layout = bounds.treemap(&WEIGHTS);
+	for rect in &layout {
+		assert!(rect.x >= 0.0 && rect.y >= 0.0, "{rect:?}");
+		assert!(rect.right() <= 100.0 + 1e-9 && rect.bottom() <= 30.0 + 1e-9, "{rect:?}");
+	}
+	for (i, a) in layout.iter().enumerate() {
+		for b in &layout[i + 1..] {
+			let shared = a.intersection(b).map_or(0.0, |common| common.area());
+			assert!(shared < 1e-9, "{a:?} and {b:?} share {shared}");
+		}
+	}
+	assert!(close(layout.iter().map(|rect| rect.area()).sum(), 3000.0));
+}
+
+#[test]
+fn the_example_from_the_paper() {
+	// 6 by 4 with these weights starts with the two 6s stacked in a column down the left edge
+	let layout = Rectangle::new(6, 4).treemap(&[6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0]);
+	assert_eq!(layout[0], PositionedRectangle::new(0.0, 0.0, Rectangle::new(3.0, 2.0)));
+	assert_eq!(layout[1], PositionedRectangle::new(0.0, 2.0, Rectangle::new(3.0, 2.0)));
+}
+
+#[test]
+fn weights_with_no_size_get_an_empty_piece() {
+	let empty = PositionedRectangle::new(0.0, 0.0, Rectangle::square(0.0));
+	let layout = Rectangle::new(10, 10).treemap(&[1.0, 0.0, -3.0, f64::NAN, 1.0]);
+	assert_eq!(&layout[1..4], [empty; 3]);
+	assert!(close(layout[0].area(), 50.0) && close(layout[4].area(), 50.0));
+
+	assert_eq!(Rectangle::new(10, 10).treemap(&[0.0, 0.0]), [empty; 2]);
+	assert_eq!(Rectangle::new(0, 10).treemap(&[1.0]), [empty]);
+	assert!(Rectangle::new(10, 10).treemap(&[]).is_empty());
+}
+
+#[test]
+fn pixel_layouts_have_no_gaps_or_overlaps() {
+	for bounds in [Rectangle::new(100, 30), Rectangle::new(7, 500), Rectangle::new(33, 33)] {
+		let layout = bounds.treemap_pixels(&WEIGHTS);
+		let measured = coverage::measure(&layout);
+		assert_eq!(measured.overlap_area, 0, "{bounds:?}");
+		assert_eq!(measured.union_area, u128::from(bounds.area()), "{bounds:?}");
+	}
+}
+
+#[test]
+fn pixel_areas_are_proportional_to_within_a_pixel_per_edge() {
+	let bounds = Rectangle::new(100, 30);
+	let exact = bounds.treemap(&WEIGHTS);
+	for (pixels, rect) in bounds.treemap_pixels(&WEIGHTS).iter().zip(&exact) {
+		// each edge moves by at most half a pixel, so each side by at most one
+		let most = (rect.width + 1.0) * (rect.height + 1.0);
+		let least = (rect.width - 1.0).max(0.0) * (rect.height - 1.0).max(0.0);
+		let area = pixels.area() as f64;
+		assert!(least <= area && area <= most, "{pixels:?} for {rect:?}");
+	}
+}
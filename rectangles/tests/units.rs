@@ -0,0 +1,57 @@
+// conversions between units and the arithmetic that keeps them apart, mostly with values picked so the floats come out exact
+use rectangles::units::{inches, mm, px, Area, Dpi, In, Mm, Px};
+use rectangles::{PositionedRectangle, Rectangle};
+
+#[test]
+fn converting_lengths() {
+	let dpi = Dpi::default();
+	assert_eq!(inches(1.0).to::<Mm>(dpi), mm(25.4));
+	assert_eq!(inches(2.0).to::<Px>(dpi), px(192.0));
+	assert_eq!(px(48.0).to::<In>(dpi), inches(0.5));
+	assert_eq!(mm(254.0).to::<In>(dpi), inches(10.0));
+	// pixels depend on the resolution, millimetres and inches don't
+	assert_eq!(inches(1.0).to::<Px>(Dpi(300.0)), px(300.0));
+	assert_eq!(mm(25.4).to::<In>(Dpi(300.0)), inches(1.0));
+	// and going there and back is the same length again
+	assert_eq!(mm(50.8).to::<Px>(dpi).to::<Mm>(dpi), mm(50.8));
+}
+
+#[test]
+fn length_arithmetic_keeps_the_unit() {
+	assert_eq!(mm(10.0) + mm(2.5), mm(12.5));
+	assert_eq!(mm(10.0) - mm(2.5), mm(7.5));
+	assert_eq!(mm(10.0) * 1.5, mm(15.0));
+	assert!(mm(1.0) < mm(2.0));
+	assert_eq!(mm(12.5).to_string(), "12.5mm");
+	assert_eq!(px(3.0).to_string(), "3px");
+}
+
+#[test]
+fn length_times_length_is_an_area() {
+	let area: Area<Mm> = mm(2.0) * mm(3.0);
+	assert_eq!(area, Area::new(6.0));
+	assert_eq!(area + Area::new(1.0), Area::new(7.0));
+	assert_eq!(area - Area::new(1.0), Area::new(5.0));
+	assert_eq!(area * 0.5, Area::new(3.0));
+	assert_eq!(area.to_string(), "6mm²");
+	// a square inch is 25.4 * 25.4 square millimetres, not 25.4
+	assert_eq!(Area::<In>::new(1.0).to::<Mm>(Dpi::default()), Area::new(645.16));
+	assert_eq!(Area::<In>::new(2.0).to::<Px>(Dpi::default()), Area::new(2.0 * 96.0 * 96.0));
+}
+
+#[test]
+fn rectangles_in_units() {
+	let a4 = Rectangle::new(mm(210.0), mm(297.0));
+	assert_eq!(a4.area(), Area::new(62370.0));
+	assert!(a4.can_hold(&Rectangle::new(mm(100.0), mm(100.0))));
+	let letter = Rectangle::new(inches(8.5), inches(11.0)).to::<Mm>(Dpi::default());
+	// 8.5 * 25.4 isn't exact in binary
+	assert!((letter.width.value - 215.9).abs() < 1e-9 && letter.height == mm(279.4));
+	assert!(!a4.can_fit(&letter));
+
+	let placed = PositionedRectangle::new(inches(1.0), inches(0.5), Rectangle::new(inches(2.0), inches(1.0)));
+	let in_pixels = placed.to::<Px>(Dpi::default());
+	assert_eq!(in_pixels, PositionedRectangle::new(px(96.0), px(48.0), Rectangle::new(px(192.0), px(96.0))));
+	assert_eq!(in_pixels.right(), px(288.0));
+	assert_eq!(in_pixels.area(), Area::new(192.0 * 96.0));
+}
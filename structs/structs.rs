@@ -70,7 +70,9 @@ fn main(){
     type from other tuples, and when naming each field as in a regular struct would be verbose or redundant.
   */
 
-  struct Color(i32, i32, i32);
+  // u8 rather than i32, a colour channel only goes from 0 to 255 so now it can't be anything else
+  // (enums/src/color.rs has the whole thing as Rgb, with hex, HSL and the CSS names)
+  struct Color(u8, u8, u8);
   struct Point(i32, i32, i32);
   let black = Color(0, 0, 0);
   let origin = Point(0, 0, 0);